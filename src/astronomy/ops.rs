@@ -1,7 +1,8 @@
 use chrono::{DateTime, Duration, Utc};
 
 use crate::astronomy::unit::{Angle, Coordinates};
-use crate::astronomy::unit::{Normalize, Stride};
+use crate::astronomy::unit::Normalize;
+use crate::models::twilight::Twilight;
 
 // The geometric mean longitude of the sun.
 pub fn mean_solar_longitude(julian_century: f64) -> Angle {
@@ -151,6 +152,20 @@ pub fn altitude_of_celestial_body(
     Angle::from_radians((term1 + term2).asin())
 }
 
+pub fn azimuth_of_celestial_body(
+    observer_latitude: Angle,
+    declination: Angle,
+    local_hour_angle: Angle,
+) -> Angle {
+    // Equation from Astronomical Algorithms page 93, measured westward from the south
+    let term1 = local_hour_angle.radians().sin();
+    let term2 = local_hour_angle.radians().cos() * observer_latitude.radians().sin()
+        - declination.radians().tan() * observer_latitude.radians().cos();
+
+    // Measured clockwise from the north instead
+    (Angle::from_radians(term1.atan2(term2)) + Angle::new(180.0)).unwound()
+}
+
 pub fn approximate_transit(longitude: Angle, sidereal_time: Angle, right_ascension: Angle) -> f64 {
     // Equation from page Astronomical Algorithms 102
     let longitude_angle = longitude * Angle::new(-1.0);
@@ -347,7 +362,7 @@ fn twilight_adjustments(daytime: AdjustmentDaytime, latitude: f64, dyy: f64) ->
 #[derive(PartialEq, Debug, Copy, Clone)]
 enum AdjustmentDaytime {
     Morning,
-    Evening,
+    Evening(Twilight),
 }
 
 #[derive(PartialEq, Debug, Copy, Clone)]
@@ -362,20 +377,27 @@ fn twilight_adjustment_values(
     daytime: AdjustmentDaytime,
     latitude: f64,
 ) -> TwilightAdjustmentValues {
-    if daytime == AdjustmentDaytime::Morning {
-        TwilightAdjustmentValues {
+    match daytime {
+        AdjustmentDaytime::Morning => TwilightAdjustmentValues {
             a: 75.0 + ((28.65 / 55.0) * latitude.abs()),
             b: 75.0 + ((19.44 / 55.0) * latitude.abs()),
             c: 75.0 + ((32.74 / 55.0) * latitude.abs()),
             d: 75.0 + ((48.10 / 55.0) * latitude.abs()),
-        }
-    } else {
-        TwilightAdjustmentValues {
+        },
+        // Shafaq general, a combination of red and white twilight
+        AdjustmentDaytime::Evening(Twilight::Red) => TwilightAdjustmentValues {
             a: 75.0 + ((25.60 / 55.0) * latitude.abs()),
             b: 75.0 + ((2.050 / 55.0) * latitude.abs()),
             c: 75.0 - ((9.210 / 55.0) * latitude.abs()),
             d: 75.0 + ((6.140 / 55.0) * latitude.abs()),
-        }
+        },
+        // Shafaq abyad, white twilight
+        AdjustmentDaytime::Evening(Twilight::White) => TwilightAdjustmentValues {
+            a: 75.0 + ((25.60 / 55.0) * latitude.abs()),
+            b: 75.0 + ((7.160 / 55.0) * latitude.abs()),
+            c: 75.0 + ((36.84 / 55.0) * latitude.abs()),
+            d: 75.0 + ((81.84 / 55.0) * latitude.abs()),
+        },
     }
 }
 
@@ -386,16 +408,15 @@ pub fn season_adjusted_evening_twilight(
     day: u32,
     year: u32,
     sunset: DateTime<Utc>,
+    twilight: Twilight,
 ) -> DateTime<Utc> {
     let dyy = days_since_solstice(day, year, latitude) as f64;
-    let adjustment = twilight_adjustments(AdjustmentDaytime::Evening, latitude, dyy);
+    let adjustment = twilight_adjustments(AdjustmentDaytime::Evening(twilight), latitude, dyy);
 
     let rounded_adjustment = (adjustment * 60.0).round() as i64;
-    let adjusted_date = sunset
+    sunset
         .checked_add_signed(Duration::seconds(rounded_adjustment))
-        .unwrap();
-
-    adjusted_date.nearest_minute()
+        .unwrap()
 }
 
 // Solstice calculation to determine a date's seasonal progression.
@@ -413,11 +434,32 @@ pub fn days_since_solstice(day_of_year: u32, year: u32, latitude: f64) -> u32 {
             lapsed_days
         }
     } else {
+        // Seasons are flipped, the count starts at the June solstice
         let southern_offset = if is_leap_year(year) { 173 } else { 172 };
-        (day_of_year - southern_offset) + days_in_year
+
+        if day_of_year >= southern_offset {
+            day_of_year - southern_offset
+        } else {
+            day_of_year + days_in_year - southern_offset
+        }
     }
 }
 
+// Atmospheric refraction (in arc minutes) at the horizon, 34' in the standard atmosphere
+// (1010 hPa and 10°C), scaled by the given pressure (in hPa) and temperature (in °C).
+pub fn horizon_refraction(pressure_hpa: f64, temperature_c: f64) -> f64 {
+    34.0 * (pressure_hpa / 1010.0) * (283.0 / (273.0 + temperature_c))
+}
+
+// Portion of the night needed by the sun to go down from the horizon to the given angle,
+// based on its vertical angular velocity at the equinox (15 * cos(latitude) degrees per hour
+// over a 12 hours night). Capped to the middle of the night.
+pub fn twilight_night_portion(angle: f64, latitude: f64) -> f64 {
+    let angular_velocity = 15.0 * Angle::new(latitude).radians().cos();
+
+    (angle / angular_velocity / 12.0).min(0.5)
+}
+
 pub fn adjust_time(date: &DateTime<Utc>, minutes: i64) -> DateTime<Utc> {
     date.checked_add_signed(Duration::seconds(minutes * 60))
         .unwrap()
@@ -426,6 +468,7 @@ pub fn adjust_time(date: &DateTime<Utc>, minutes: i64) -> DateTime<Utc> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn calculate_julian_day() {
@@ -575,6 +618,60 @@ mod tests {
         assert_eq!(celestial_body.degrees, -0.900_615_621_559_432_1);
     }
 
+    #[test]
+    fn season_adjusted_evening_twilight_for_white_twilight() {
+        let sunset = Utc.ymd(2022, 6, 1).and_hms(20, 0, 0);
+        let red = season_adjusted_evening_twilight(51.5, 152, 2022, sunset, Twilight::Red);
+        let white = season_adjusted_evening_twilight(51.5, 152, 2022, sunset, Twilight::White);
+
+        assert!(white > red);
+    }
+
+    #[test]
+    fn days_since_solstice_in_both_hemispheres() {
+        assert_eq!(days_since_solstice(1, 2022, 33.87), 11);
+        assert_eq!(days_since_solstice(360, 2022, 33.87), 5);
+        assert_eq!(days_since_solstice(172, 2022, -33.87), 0);
+        assert_eq!(days_since_solstice(183, 2022, -33.87), 11);
+        assert_eq!(days_since_solstice(1, 2022, -33.87), 194);
+        assert_eq!(days_since_solstice(1, 2024, -33.87), 194);
+    }
+
+    #[test]
+    fn season_adjusted_twilight_flips_in_southern_hemisphere() {
+        let sunrise = Utc.ymd(2022, 6, 21).and_hms(6, 0, 0);
+        let sunset = Utc.ymd(2022, 6, 21).and_hms(18, 0, 0);
+
+        // At the June solstice, it's summer in the north and winter in the south
+        let north = season_adjusted_morning_twilight(33.87, 172, 2022, sunrise);
+        let south = season_adjusted_morning_twilight(-33.87, 172, 2022, sunrise);
+        assert!(north < south);
+
+        // A southern date matches the northern date six months apart
+        assert_eq!(
+            season_adjusted_morning_twilight(-33.87, 183, 2022, sunrise),
+            season_adjusted_morning_twilight(33.87, 1, 2022, sunrise)
+        );
+        assert_eq!(
+            season_adjusted_evening_twilight(-33.87, 183, 2022, sunset, Twilight::Red),
+            season_adjusted_evening_twilight(33.87, 1, 2022, sunset, Twilight::Red)
+        );
+    }
+
+    #[test]
+    fn calculate_horizon_refraction() {
+        assert_eq!(horizon_refraction(1010.0, 10.0), 34.0);
+        assert!(horizon_refraction(1050.0, 10.0) > 34.0);
+        assert!(horizon_refraction(1010.0, 30.0) < 34.0);
+    }
+
+    #[test]
+    fn calculate_twilight_night_portion() {
+        assert!((twilight_night_portion(18.0, 0.0) - 0.1).abs() < 1e-9);
+        assert!(twilight_night_portion(18.0, 55.0) > twilight_night_portion(18.0, 45.0));
+        assert_eq!(twilight_night_portion(18.0, 80.0), 0.5);
+    }
+
     macro_rules! leap_year_tests {
         ($($name:ident: $value:expr,)*) => {
         $(
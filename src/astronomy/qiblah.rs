@@ -1,39 +1,213 @@
+use std::fmt;
+
+use chrono::{Date, DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+
+use crate::astronomy::solar::SolarTime;
 use crate::astronomy::unit::{Angle, Coordinates};
+use crate::constants::{KAABA_COORDINATES, KILOMETERS_PER_MILE};
 
-// Direction to Mekkah
-pub struct Qiblah(f64);
+/// Direction to Mekkah, expressed as the initial great-circle bearing
+/// (clockwise from the true north) from the given location to the Kaaba,
+/// or to another target (e.g. Al-Aqsa, the first qiblah).
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Qiblah {
+    direction: f64,
+    location: Coordinates,
+    target: Coordinates,
+}
 
 impl Qiblah {
     pub fn new(location_coordinates: Coordinates) -> Self {
+        Qiblah::toward(location_coordinates, KAABA_COORDINATES)
+    }
+
+    /// Returns the direction from the given location to the given target
+    pub fn toward(location_coordinates: Coordinates, target: Coordinates) -> Self {
         // Equation from "Spherical Trigonometry For the use
         // of colleges and schools" page 50
-        let kaaba_coordinates = Coordinates::new(21.4225241, 39.8261818);
-        let term1 = (kaaba_coordinates.longitude_angle().radians()
+        let term1 = (target.longitude_angle().radians()
             - location_coordinates.longitude_angle().radians())
         .sin();
-        let term2 = kaaba_coordinates.latitude_angle().radians().tan()
+        let term2 = target.latitude_angle().radians().tan()
             * location_coordinates.latitude_angle().radians().cos();
-        let term3 = (kaaba_coordinates.longitude_angle().radians()
+        let term3 = (target.longitude_angle().radians()
             - location_coordinates.longitude_angle().radians())
         .cos()
             * location_coordinates.latitude_angle().radians().sin();
         let term4 = term1.atan2(term2 - term3);
 
-        Qiblah(Angle::from_radians(term4).unwound().degrees)
+        Qiblah {
+            direction: Angle::from_radians(term4).unwound().degrees,
+            location: location_coordinates,
+            target,
+        }
+    }
+
+    /// Returns the qiblah direction, in the range [0, 360)
+    pub fn direction(&self) -> Angle {
+        Angle::new(self.direction)
+    }
+
+    /// Returns the final bearing, i.e. the direction of the great-circle path
+    /// as it arrives at the Kaaba (or the target), in the range [0, 360)
+    pub fn final_bearing(&self) -> Angle {
+        let reverse = Qiblah::toward(self.target, self.location);
+
+        (reverse.direction() + Angle::new(180.0)).unwound()
+    }
+
+    /// Returns the closest of the 16 compass points to the qiblah direction (e.g. "ESE")
+    pub fn cardinal(&self) -> &'static str {
+        let points = [
+            "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+            "NW", "NNW",
+        ];
+        let index = ((self.direction + 11.25) / 22.5).floor() as usize % points.len();
+
+        points[index]
+    }
+
+    /// Returns the qiblah direction relative to the magnetic north, given the magnetic
+    /// declination at the location (positive when the magnetic north is east of the true north)
+    pub fn magnetic_direction(&self, declination: Angle) -> Angle {
+        (self.direction() - declination).unwound()
+    }
+
+    /// Returns the distance to the Kaaba (or the target) in kilometers
+    pub fn distance(&self) -> f64 {
+        self.location.distance_to(self.target)
+    }
+
+    /// Returns the distance to the Kaaba (or the target) in miles
+    pub fn distance_miles(&self) -> f64 {
+        self.distance() / KILOMETERS_PER_MILE
+    }
+
+    /// Returns the instants (Rashdul Qibla) of the given date's year when the sun is
+    /// directly above the Kaaba (around May 28 and July 16), at these times the shadow
+    /// of any vertical object points away from the qiblah.
+    pub fn solar_alignment_times(date: Date<Utc>) -> Vec<DateTime<Utc>> {
+        let first_day = Utc.ymd(date.year(), 1, 1).and_hms(0, 0, 0);
+        let days = Utc
+            .ymd(date.year() + 1, 1, 1)
+            .signed_duration_since(first_day.date());
+        let solar_times: Vec<SolarTime> = (0..=days.num_days())
+            .map(|day| SolarTime::new(first_day + Duration::days(day), KAABA_COORDINATES))
+            .collect();
+
+        // Angle between the sun and the zenith of the Kaaba at the transit of each day
+        let offsets: Vec<(DateTime<Utc>, f64)> = solar_times
+            .windows(2)
+            .filter_map(|solar_times| {
+                let transit = solar_times[0].transit?;
+                let day_fraction = transit.num_seconds_from_midnight() as f64 / 86400.0;
+                let declination = solar_times[0].declination().degrees
+                    + (solar_times[1].declination().degrees - solar_times[0].declination().degrees)
+                        * day_fraction;
+
+                Some((transit, declination - KAABA_COORDINATES.latitude))
+            })
+            .collect();
+
+        // The sun crosses the Kaaba latitude when the offset changes its sign
+        offsets
+            .windows(2)
+            .filter(|days| days[0].1.signum() != days[1].1.signum())
+            .map(|days| {
+                if days[0].1.abs() < days[1].1.abs() {
+                    days[0].0
+                } else {
+                    days[1].0
+                }
+            })
+            .collect()
     }
 }
 
+impl fmt::Display for Qiblah {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1}°", self.direction)
+    }
+}
 #[cfg(test)]
 mod tests {
     use super::*;
     use spectral::prelude::*;
 
+    #[test]
+    fn qiblah_cardinal_direction() {
+        let cardinal = |direction| {
+            Qiblah {
+                direction,
+                location: Coordinates::new(36.8065, 10.1815),
+                target: KAABA_COORDINATES,
+            }
+            .cardinal()
+        };
+
+        assert_eq!(cardinal(0.0), "N");
+        assert_eq!(cardinal(45.0), "NE");
+        assert_eq!(cardinal(118.0), "ESE");
+        assert_eq!(cardinal(180.0), "S");
+        assert_eq!(cardinal(350.0), "N");
+        assert_eq!(cardinal(337.0), "NNW");
+        assert_eq!(
+            Qiblah::new(Coordinates::new(40.7128, -74.0059)).cardinal(),
+            "ENE"
+        );
+    }
+
+    #[test]
+    fn qiblah_magnetic_direction() {
+        let qiblah = Qiblah {
+            direction: 120.0,
+            location: Coordinates::new(36.8065, 10.1815),
+            target: KAABA_COORDINATES,
+        };
+
+        assert_eq!(
+            qiblah.magnetic_direction(Angle::new(5.0)),
+            Angle::new(115.0)
+        );
+        assert_eq!(
+            qiblah.magnetic_direction(Angle::new(-5.0)),
+            Angle::new(125.0)
+        );
+    }
+
+    #[test]
+    fn qiblah_magnetic_direction_wraps_around_north() {
+        let qiblah = Qiblah {
+            direction: 3.0,
+            location: Coordinates::new(36.8065, 10.1815),
+            target: KAABA_COORDINATES,
+        };
+
+        assert_eq!(
+            qiblah.magnetic_direction(Angle::new(5.0)),
+            Angle::new(358.0)
+        );
+    }
+
+    #[test]
+    fn solar_alignment_times_above_the_kaaba() {
+        let times = Qiblah::solar_alignment_times(Utc.ymd(2022, 3, 1));
+
+        assert_eq!(times.len(), 2);
+        assert_eq!(times[0].month(), 5);
+        assert!([27, 28].contains(&times[0].day()));
+        assert_eq!(times[0].hour(), 9);
+        assert_eq!(times[1].month(), 7);
+        assert!([15, 16].contains(&times[1].day()));
+        assert_eq!(times[1].hour(), 9);
+    }
+
     #[test]
     fn qiblah_direction_from_nyc_in_north_america() {
         let nyc = Coordinates::new(40.7128, -74.0059);
         let qiblah = Qiblah::new(nyc);
 
-        assert_that!(qiblah.0).is_close_to(58.4817635, 0.0000001f64);
+        assert_that!(qiblah.direction().degrees).is_close_to(58.4817635, 0.0000001f64);
     }
 
     #[test]
@@ -41,7 +215,7 @@ mod tests {
         let sf = Coordinates::new(37.7749, -122.4194);
         let qiblah = Qiblah::new(sf);
 
-        assert_eq!(qiblah.0, 18.843822245692426);
+        assert_eq!(qiblah.direction().degrees, 18.843822245692426);
     }
 
     #[test]
@@ -49,7 +223,7 @@ mod tests {
         let dc = Coordinates::new(38.9072, -77.0369);
         let qiblah = Qiblah::new(dc);
 
-        assert_eq!(qiblah.0, 56.56046821463599);
+        assert_eq!(qiblah.direction().degrees, 56.56046821463599);
     }
 
     #[test]
@@ -57,7 +231,7 @@ mod tests {
         let dc = Coordinates::new(61.2181, -149.9003);
         let qiblah = Qiblah::new(dc);
 
-        assert_eq!(qiblah.0, 350.8830761159853);
+        assert_eq!(qiblah.direction().degrees, 350.8830761159853);
     }
 
     #[test]
@@ -65,7 +239,7 @@ mod tests {
         let sydney = Coordinates::new(-33.8688, 151.2093);
         let qiblah = Qiblah::new(sydney);
 
-        assert_eq!(qiblah.0, 277.4996044487399);
+        assert_eq!(qiblah.direction().degrees, 277.4996044487399);
     }
 
     #[test]
@@ -73,7 +247,7 @@ mod tests {
         let auckland = Coordinates::new(-36.8485, 174.7633);
         let qiblah = Qiblah::new(auckland);
 
-        assert_eq!(qiblah.0, 261.19732640365845);
+        assert_eq!(qiblah.direction().degrees, 261.19732640365845);
     }
 
     #[test]
@@ -81,7 +255,7 @@ mod tests {
         let london = Coordinates::new(51.5074, -0.1278);
         let qiblah = Qiblah::new(london);
 
-        assert_that!(qiblah.0).is_close_to(118.9872189, 0.0000001f64);
+        assert_that!(qiblah.direction().degrees).is_close_to(118.9872189, 0.0000001f64);
     }
 
     #[test]
@@ -89,7 +263,7 @@ mod tests {
         let paris = Coordinates::new(48.8566, 2.3522);
         let qiblah = Qiblah::new(paris);
 
-        assert_eq!(qiblah.0, 119.16313542183347);
+        assert_eq!(qiblah.direction().degrees, 119.16313542183347);
     }
 
     #[test]
@@ -97,7 +271,7 @@ mod tests {
         let oslo = Coordinates::new(59.9139, 10.7522);
         let qiblah = Qiblah::new(oslo);
 
-        assert_eq!(qiblah.0, 139.02785605537514);
+        assert_eq!(qiblah.direction().degrees, 139.02785605537514);
     }
 
     #[test]
@@ -105,7 +279,7 @@ mod tests {
         let islamabad = Coordinates::new(33.7294, 73.0931);
         let qiblah = Qiblah::new(islamabad);
 
-        assert_eq!(qiblah.0, 255.8816156785436);
+        assert_eq!(qiblah.direction().degrees, 255.8816156785436);
     }
 
     #[test]
@@ -113,7 +287,7 @@ mod tests {
         let tokyo = Coordinates::new(35.6895, 139.6917);
         let qiblah = Qiblah::new(tokyo);
 
-        assert_eq!(qiblah.0, 293.02072441441163);
+        assert_eq!(qiblah.direction().degrees, 293.02072441441163);
     }
 
     #[test]
@@ -121,6 +295,70 @@ mod tests {
         let jakarta = Coordinates::new(-6.18233995, 106.84287154);
         let qiblah = Qiblah::new(jakarta);
 
-        assert_that!(qiblah.0).is_close_to(295.1442983825265, 0.0000001f64);
+        assert_that!(qiblah.direction().degrees).is_close_to(295.1442983825265, 0.0000001f64);
+    }
+
+    #[test]
+    fn qiblah_direction_from_tunis_tunisia() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let qiblah = Qiblah::new(tunis);
+
+        assert_that!(qiblah.direction().degrees).is_close_to(112.6657202, 0.0000001f64);
+    }
+
+    #[test]
+    fn qiblah_direction_is_normalized() {
+        let jakarta = Coordinates::new(-6.18233995, 106.84287154);
+        let direction = Qiblah::new(jakarta).direction();
+
+        // Jakarta points roughly north-west
+        assert!(direction.degrees > 270.0 && direction.degrees < 360.0);
+    }
+
+    #[test]
+    fn direction_from_makkah_to_al_aqsa() {
+        let al_aqsa = Coordinates::new(31.7761, 35.2358);
+        let qiblah = Qiblah::toward(KAABA_COORDINATES, al_aqsa);
+
+        assert_that!(qiblah.direction().degrees).is_close_to(339.37, 0.01f64);
+        assert_eq!(qiblah.cardinal(), "NNW");
+        assert_that!(qiblah.distance()).is_close_to(1238.0, 1.0f64);
+        assert_eq!(
+            Qiblah::toward(KAABA_COORDINATES, KAABA_COORDINATES).distance(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn final_bearing_from_auckland() {
+        let qiblah = Qiblah::new(Coordinates::new(-36.8485, 174.7633));
+        let difference = (qiblah.final_bearing() - qiblah.direction()).degrees.abs();
+
+        assert_that!(qiblah.final_bearing().degrees).is_close_to(301.844, 0.001f64);
+        assert!(difference > 40.0);
+
+        // On the same meridian, the bearing does not change
+        let north = Qiblah::new(Coordinates::new(-10.0, KAABA_COORDINATES.longitude));
+        assert_that!(north.final_bearing().degrees).is_close_to(0.0, 0.000001f64);
+    }
+
+    #[test]
+    fn qiblah_display_with_one_decimal() {
+        let london = Coordinates::new(51.5074, -0.1278);
+        let qiblah = Qiblah::new(london);
+
+        assert_eq!(qiblah.to_string(), "119.0°");
+    }
+
+    #[test]
+    fn distance_to_kaaba() {
+        let london = Qiblah::new(Coordinates::new(51.5074, -0.1278));
+        let nyc = Qiblah::new(Coordinates::new(40.7128, -74.0059));
+        let tunis = Qiblah::new(Coordinates::new(36.8065, 10.1815));
+
+        assert_that!(london.distance()).is_close_to(4793.8, 1.0f64);
+        assert_that!(nyc.distance()).is_close_to(10306.3, 1.0f64);
+        assert_that!(tunis.distance()).is_close_to(3328.4, 1.0f64);
+        assert_that!(london.distance_miles()).is_close_to(2978.7, 1.0f64);
     }
 }
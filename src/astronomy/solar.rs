@@ -1,8 +1,9 @@
-use chrono::{DateTime, Datelike, TimeZone, Utc};
+use chrono::{Date, DateTime, Datelike, Duration, TimeZone, Utc};
 
 use crate::astronomy::ops;
 use crate::astronomy::unit::Stride;
 use crate::astronomy::unit::{Angle, Coordinates};
+use crate::constants::{STANDARD_PRESSURE_HPA, STANDARD_TEMPERATURE_C};
 
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct SolarCoordinates {
@@ -16,6 +17,10 @@ pub struct SolarCoordinates {
 
     // Apparent sidereal time, the hour angle of the vernal equinox.
     apparent_sidereal_time: Angle,
+
+    // Equation of time, the difference (in minutes) between
+    // the apparent and the mean solar time.
+    equation_of_time: f64,
 }
 
 impl SolarCoordinates {
@@ -65,10 +70,23 @@ impl SolarCoordinates {
                     / 3600.0,
         );
 
+        // Equation from Astronomical Algorithms page 185
+        let equation_of_time = Angle::new(
+            mean_solar_longitude.degrees - 0.0057183 - right_ascension.degrees
+                + nutation_longitude
+                    * Angle::new(mean_obliq_ecliptic.degrees + nutation_obliq)
+                        .radians()
+                        .cos(),
+        )
+        .quadrant_shifted()
+        .degrees
+            * 4.0;
+
         SolarCoordinates {
             declination,
             right_ascension,
             apparent_sidereal_time,
+            equation_of_time,
         }
     }
 }
@@ -85,10 +103,31 @@ pub struct SolarTime {
     prev_solar: SolarCoordinates,
     next_solar: SolarCoordinates,
     approx_transit: f64,
+    /// Shift applied to the times at which the sun reaches an altitude
+    time_offset: Duration,
 }
 
 impl SolarTime {
     pub fn new(date: DateTime<Utc>, coordinates: Coordinates) -> SolarTime {
+        SolarTime::with_atmosphere(
+            date,
+            coordinates,
+            STANDARD_PRESSURE_HPA,
+            STANDARD_TEMPERATURE_C,
+        )
+    }
+
+    /// Computes the solar time for the given atmospheric pressure (in hPa)
+    /// and temperature (in °C), which affect the refraction at sunrise and sunset
+    ///
+    /// Out of range coordinates are normalized, see [Coordinates::normalized](../unit/struct.Coordinates.html#method.normalized).
+    pub fn with_atmosphere(
+        date: DateTime<Utc>,
+        coordinates: Coordinates,
+        pressure_hpa: f64,
+        temperature_c: f64,
+    ) -> SolarTime {
+        let coordinates = coordinates.normalized();
         // All calculation need to occur at 0h0m UTC
         let today = Utc
             .ymd(date.year(), date.month(), date.day())
@@ -98,12 +137,20 @@ impl SolarTime {
         let prev_solar = SolarCoordinates::new(yesterday.julian_day());
         let solar = SolarCoordinates::new(today.julian_day());
         let next_solar = SolarCoordinates::new(tomorrow.julian_day());
-        let solar_altitude = Angle::new(-50.0 / 60.0);
+        // The upper limb of the sun (16' semi-diameter) touches the refracted horizon,
+        // observers above sea level see the sun rise earlier and set later
+        let solar_altitude =
+            Angle::new(-(16.0 + ops::horizon_refraction(pressure_hpa, temperature_c)) / 60.0)
+                - coordinates.horizon_dip();
         let approx_transit = ops::approximate_transit(
             coordinates.longitude_angle(),
             solar.apparent_sidereal_time,
             solar.right_ascension,
         );
+        // The approximate transit is a fraction of the UTC day, close to the antimeridian
+        // the local solar noon falls on the previous (east) or the next (west) UTC day
+        let local_noon = 0.5 - coordinates.longitude / 360.0;
+        let approx_transit = approx_transit + (local_noon - approx_transit).round();
         let transit_time = ops::corrected_transit(
             approx_transit,
             coordinates.longitude_angle(),
@@ -149,10 +196,166 @@ impl SolarTime {
             prev_solar,
             next_solar,
             approx_transit,
+            time_offset: Duration::zero(),
+        }
+    }
+
+    /// Returns a copy of this solar time following the mean sun instead of the true sun,
+    /// i.e. without the equation of time correction: the transit is at the mean solar noon
+    /// (12:00 local mean time), and all times are shifted by the equation of time.
+    ///
+    /// The sun position is not affected.
+    pub fn to_mean_time(&self) -> SolarTime {
+        let offset = Duration::seconds((self.equation_of_time() * 60.0).round() as i64);
+        let shift = offset - self.time_offset;
+
+        SolarTime {
+            transit: self.transit.map(|transit| transit + shift),
+            sunrise: self.sunrise.map(|sunrise| sunrise + shift),
+            sunset: self.sunset.map(|sunset| sunset + shift),
+            time_offset: offset,
+            ..*self
         }
     }
 
+    /// Returns the declination of the sun
+    pub fn declination(&self) -> Angle {
+        self.solar.declination
+    }
+
+    /// Returns true if the sun does not set, i.e. midnight sun
+    pub fn is_polar_day(&self) -> bool {
+        !self.has_sunrise_and_sunset() && self.transit_altitude() > 0.0
+    }
+
+    /// Returns true if the sun does not rise
+    pub fn is_polar_night(&self) -> bool {
+        !self.has_sunrise_and_sunset() && self.transit_altitude() <= 0.0
+    }
+
+    /// Returns the ranges of dates (inclusive) of the given year during which
+    /// the sun does not set (polar day) or does not rise (polar night) at the given location
+    pub fn polar_periods(year: i32, coordinates: Coordinates) -> Vec<(Date<Utc>, Date<Utc>)> {
+        let mut periods: Vec<(Date<Utc>, Date<Utc>)> = Vec::new();
+        // Whether the previous day is a polar day (true) or a polar night (false)
+        let mut previous_polar_day: Option<bool> = None;
+        let first_day = Utc.ymd(year, 1, 1);
+
+        for days in 0..366 {
+            let date = first_day + Duration::days(days);
+            if date.year() != year {
+                break;
+            }
+
+            let solar_time = SolarTime::new(date.and_hms(0, 0, 0), coordinates);
+            let polar_day = match (solar_time.is_polar_day(), solar_time.is_polar_night()) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+
+            match (polar_day, previous_polar_day) {
+                (Some(polar_day), Some(previous)) if polar_day == previous => {
+                    if let Some(period) = periods.last_mut() {
+                        period.1 = date;
+                    }
+                }
+                (Some(_), _) => periods.push((date, date)),
+                _ => {}
+            }
+            previous_polar_day = polar_day;
+        }
+
+        periods
+    }
+
+    fn has_sunrise_and_sunset(&self) -> bool {
+        self.sunrise.is_some() && self.sunset.is_some()
+    }
+
+    /// Returns the altitude (in degrees) of the sun at its transit
+    fn transit_altitude(&self) -> f64 {
+        90.0 - (self.observer.latitude - self.solar.declination.degrees).abs()
+    }
+
+    /// Returns the equation of time in minutes
+    pub fn equation_of_time(&self) -> f64 {
+        self.solar.equation_of_time
+    }
+
+    /// Returns the position of the sun at the given time as (azimuth, altitude),
+    /// the azimuth is measured clockwise from the north, and the altitude
+    /// is the geometric altitude above the horizon, without atmospheric refraction.
+    pub fn position(&self, at: DateTime<Utc>) -> (Angle, Angle) {
+        let midnight = self.date.date().and_hms(0, 0, 0);
+        let factor = at.signed_duration_since(midnight).num_seconds() as f64 / 86400.0;
+
+        let sidereal_time =
+            Angle::new(self.solar.apparent_sidereal_time.degrees + 360.985647 * factor);
+        let right_ascension = ops::interpolate_angles(
+            self.solar.right_ascension,
+            self.prev_solar.right_ascension,
+            self.next_solar.right_ascension,
+            factor,
+        );
+        let declination = Angle::new(ops::interpolate(
+            self.solar.declination.degrees,
+            self.prev_solar.declination.degrees,
+            self.next_solar.declination.degrees,
+            factor,
+        ));
+        let local_hour_angle =
+            (sidereal_time + self.observer.longitude_angle() - right_ascension).unwound();
+        let latitude = self.observer.latitude_angle();
+
+        (
+            ops::azimuth_of_celestial_body(latitude, declination, local_hour_angle),
+            ops::altitude_of_celestial_body(latitude, declination, local_hour_angle),
+        )
+    }
+
+    /// Returns the ratio of the length of an object's shadow to its height at the given time,
+    /// or infinity when the sun is below the horizon
+    pub fn shadow_ratio_at(&self, at: DateTime<Utc>) -> f64 {
+        let (_, altitude) = self.position(at);
+
+        if altitude.degrees <= 0.0 {
+            f64::INFINITY
+        } else {
+            1.0 / altitude.radians().tan()
+        }
+    }
+
+    /// Returns the solar midnight, i.e. the anti-transit when the sun is at its lowest,
+    /// halfway between this transit and the next one.
+    pub fn solar_midnight(&self) -> DateTime<Utc> {
+        // The transit moves earlier by the daily change of the equation of time
+        let drift = self.next_solar.equation_of_time - self.solar.equation_of_time;
+        let half_day = Duration::hours(12) - Duration::seconds((drift * 30.0) as i64);
+
+        // The transit is always defined, unlike sunrise and sunset
+        self.transit.unwrap() + half_day
+    }
+
+    /// Returns the time at which the sun reaches the given altitude,
+    /// before or after its transit
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sun does not reach the given altitude,
+    /// see [checked_time_for_solar_angle](#method.checked_time_for_solar_angle)
     pub fn time_for_solar_angle(&self, angle: Angle, after_transit: bool) -> DateTime<Utc> {
+        self.checked_time_for_solar_angle(angle, after_transit)
+            .unwrap()
+    }
+
+    /// Returns the time at which the sun reaches the given altitude,
+    /// before or after its transit, or `None` if the sun does not reach it
+    pub fn checked_time_for_solar_angle(
+        &self,
+        angle: Angle,
+        after_transit: bool,
+    ) -> Option<DateTime<Utc>> {
         let hours = ops::corrected_hour_angle(
             self.approx_transit,
             angle,
@@ -167,7 +370,24 @@ impl SolarTime {
             self.next_solar.declination,
         );
 
-        SolarTime::setting_hour(hours, &self.date).unwrap()
+        SolarTime::setting_hour(hours, &self.date).map(|time| time + self.time_offset)
+    }
+
+    /// Returns a copy of this solar time moved to the given date,
+    /// i.e. the solar geometry is kept but times fall on the given date.
+    pub(crate) fn with_date(&self, date: DateTime<Utc>) -> SolarTime {
+        let shift = date
+            .date()
+            .and_hms(0, 0, 0)
+            .signed_duration_since(self.date.date().and_hms(0, 0, 0));
+
+        SolarTime {
+            date,
+            transit: self.transit.map(|transit| transit + shift),
+            sunrise: self.sunrise.map(|sunrise| sunrise + shift),
+            sunset: self.sunset.map(|sunset| sunset + shift),
+            ..*self
+        }
     }
 
     pub fn afternoon(&self, shadow_length: f64) -> DateTime<Utc> {
@@ -191,16 +411,18 @@ impl SolarTime {
             let (adjusted_hour, adjusted_date) = SolarTime::hour_adjustment(calculated_hours, date);
 
             // Round to the nearest minute
-            let adjusted_mins = (calculated_minutes + calculated_seconds / 60.0).round() as u32;
+            let adjusted_mins = (calculated_minutes + calculated_seconds / 60.0).round() as i64;
             let adjusted_secs: u32 = 0;
 
+            // Rounding may carry over to the next hour (i.e. 60 minutes)
             let adjusted = Utc
                 .ymd(
                     adjusted_date.year(),
                     adjusted_date.month(),
                     adjusted_date.day(),
                 )
-                .and_hms(adjusted_hour, adjusted_mins, adjusted_secs);
+                .and_hms(adjusted_hour, 0, adjusted_secs)
+                + Duration::minutes(adjusted_mins);
 
             adjusted_time = Some(adjusted);
         } else {
@@ -214,13 +436,12 @@ impl SolarTime {
         // Adjust the hour to be within 0..=23,
         // wrapping around as needed; otherwise
         // chrono method will panic.
-        if calculated_hours < 0.0 {
-            ((calculated_hours + 24.0) as u32, date.yesterday())
-        } else if calculated_hours >= 24.0 {
-            ((calculated_hours - 24.0) as u32, date.tomorrow())
-        } else {
-            (calculated_hours as u32, *date)
-        }
+        let days = (calculated_hours / 24.0).floor();
+
+        (
+            (calculated_hours - days * 24.0) as u32,
+            *date + Duration::days(days as i64),
+        )
     }
 }
 
@@ -230,6 +451,42 @@ mod tests {
     use crate::astronomy::ops;
     use chrono::{Datelike, TimeZone, Utc};
 
+    #[test]
+    fn solar_time_normalizes_longitude() {
+        let date = Utc.ymd(2022, 8, 1).and_hms(0, 0, 0);
+        let honolulu = Coordinates::new(21.3069, -157.8583);
+        let wrapped = Coordinates::new(21.3069, 202.1417);
+        let solar_time = SolarTime::new(date, honolulu);
+        let wrapped_solar_time = SolarTime::new(date, wrapped);
+
+        assert_eq!(wrapped_solar_time.transit, solar_time.transit);
+        assert_eq!(wrapped_solar_time.sunrise, solar_time.sunrise);
+        assert_eq!(wrapped_solar_time.sunset, solar_time.sunset);
+    }
+
+    #[test]
+    fn mean_solar_time_in_early_november() {
+        // The equation of time is at its maximum (about 16.4 minutes) early November,
+        // the true sun transits before the mean sun
+        let greenwich = Coordinates::new(51.4769, 0.0);
+        let solar_time = SolarTime::new(Utc.ymd(2022, 11, 3).and_hms(0, 0, 0), greenwich);
+        let mean_time = solar_time.to_mean_time();
+
+        let shift = Duration::seconds((solar_time.equation_of_time() * 60.0).round() as i64);
+
+        assert_eq!(shift.num_minutes(), 16);
+        assert_eq!(
+            mean_time.transit.unwrap().nearest_minute(),
+            Utc.ymd(2022, 11, 3).and_hms(12, 0, 0)
+        );
+        assert_eq!(
+            mean_time.sunrise.unwrap() - solar_time.sunrise.unwrap(),
+            shift
+        );
+        assert_eq!(mean_time.afternoon(1.0) - solar_time.afternoon(1.0), shift);
+        assert_eq!(mean_time.to_mean_time().sunset, mean_time.sunset);
+    }
+
     #[test]
     fn solar_coordinates() {
         let julian_day = ops::julian_day(1992, 10, 13, 0.0);
@@ -243,6 +500,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn solar_equation_of_time() {
+        // Example 28.b from Astronomical Algorithms: E = 13m 42.6s
+        let julian_day = ops::julian_day(1992, 10, 13, 0.0);
+        let solar = SolarCoordinates::new(julian_day);
+
+        assert!((solar.equation_of_time - 13.71).abs() < 0.05);
+    }
+
     #[test]
     fn zero_out_time_for_a_date() {
         // Local date below is 2019-01-11T04:41:19Z in UTC
@@ -315,6 +581,26 @@ mod tests {
         assert_eq!(solar.sunset.unwrap(), sunset_date);
     }
 
+    #[test]
+    fn elevation_shifts_sunrise_and_sunset() {
+        let date = Utc.ymd(2015, 7, 12).and_hms(0, 0, 0);
+        let sea_level = SolarTime::new(
+            date,
+            Coordinates::new(35.0 + 47.0 / 60.0, -78.0 - 39.0 / 60.0),
+        );
+        let mountain = SolarTime::new(
+            date,
+            Coordinates::with_elevation(35.0 + 47.0 / 60.0, -78.0 - 39.0 / 60.0, 1000.0),
+        );
+        let sunrise_shift = sea_level.sunrise.unwrap() - mountain.sunrise.unwrap();
+        let sunset_shift = mountain.sunset.unwrap() - sea_level.sunset.unwrap();
+
+        // The horizon dip at 1000m is about 1.1 degrees
+        assert!(sunrise_shift >= Duration::minutes(4) && sunrise_shift <= Duration::minutes(8));
+        assert!(sunset_shift >= Duration::minutes(4) && sunset_shift <= Duration::minutes(8));
+        assert_eq!(mountain.transit, sea_level.transit);
+    }
+
     #[test]
     fn calculate_solar_time_tromso_polar_night() {
         // See https://www.theatlantic.com/health/archive/2015/7/the-norwegian-town-where-the-sun-doesnt-rise/396746/
@@ -342,6 +628,230 @@ mod tests {
         assert_eq!(solar.sunset, None);
     }
 
+    #[test]
+    fn detect_polar_day_and_night_in_tromso() {
+        let coordinates = Coordinates::new(69.649208, 18.955324);
+        let summer = SolarTime::new(Utc.ymd(2015, 6, 21).and_hms(0, 0, 0), coordinates);
+        let winter = SolarTime::new(Utc.ymd(2015, 12, 21).and_hms(0, 0, 0), coordinates);
+        let spring = SolarTime::new(Utc.ymd(2015, 3, 21).and_hms(0, 0, 0), coordinates);
+
+        assert!(summer.is_polar_day());
+        assert!(!summer.is_polar_night());
+        assert!(winter.is_polar_night());
+        assert!(!winter.is_polar_day());
+        assert!(!spring.is_polar_day());
+        assert!(!spring.is_polar_night());
+    }
+
+    #[test]
+    fn atmosphere_shifts_sunrise_and_sunset() {
+        // Close to the polar circle, the sun rises slowly in winter
+        let coordinates = Coordinates::new(65.0, 10.75);
+        let date = Utc.ymd(2022, 12, 10).and_hms(0, 0, 0);
+        let standard = SolarTime::new(date, coordinates);
+        let cold = SolarTime::with_atmosphere(date, coordinates, 1050.0, -20.0);
+
+        assert_eq!(
+            SolarTime::with_atmosphere(date, coordinates, 1010.0, 10.0).sunrise,
+            standard.sunrise
+        );
+        // More refraction, the sun is seen earlier and later
+        assert_eq!(
+            standard.sunrise.unwrap() - cold.sunrise.unwrap(),
+            Duration::minutes(2)
+        );
+        assert_eq!(
+            cold.sunset.unwrap() - standard.sunset.unwrap(),
+            Duration::minutes(2)
+        );
+        assert_eq!(cold.transit, standard.transit);
+    }
+
+    #[test]
+    fn transit_on_the_local_solar_day_near_the_antimeridian() {
+        // Local solar noon is around 12:00 - longitude / 15 (UTC)
+        let local_noon = |coordinates: Coordinates, date: DateTime<Utc>| {
+            date + Duration::minutes((720.0 - coordinates.longitude * 4.0) as i64)
+        };
+
+        for coordinates in [
+            Coordinates::new(-18.1416, 178.4419),
+            Coordinates::new(-16.5, 179.9),
+            Coordinates::new(-13.8333, -172.0),
+            Coordinates::new(-16.5, -179.9),
+        ] {
+            for (month, day) in [(2, 11), (8, 1), (11, 3)] {
+                let date = Utc.ymd(2022, month, day).and_hms(0, 0, 0);
+                let solar = SolarTime::new(date, coordinates);
+                let transit = solar.transit.unwrap();
+                let offset = transit - local_noon(coordinates, date);
+
+                // The equation of time is within ±17 minutes
+                assert!(offset.num_minutes().abs() <= 17, "{}", transit);
+                assert!(solar.sunrise.unwrap() < transit);
+                assert!(transit < solar.sunset.unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn polar_periods_in_longyearbyen() {
+        let coordinates = Coordinates::new(78.2232, 15.6267);
+        let periods = SolarTime::polar_periods(2022, coordinates);
+
+        assert!(!periods.is_empty());
+        assert!(periods.iter().all(|(start, end)| start <= end));
+        let midsummer = Utc.ymd(2022, 6, 21);
+        let polar_day = periods
+            .iter()
+            .find(|(start, end)| *start <= midsummer && midsummer <= *end)
+            .unwrap();
+        assert!(SolarTime::new(polar_day.0.and_hms(0, 0, 0), coordinates).is_polar_day());
+        assert!(SolarTime::new(polar_day.1.and_hms(0, 0, 0), coordinates).is_polar_day());
+        assert_eq!(periods.last().unwrap().1, Utc.ymd(2022, 12, 31));
+    }
+
+    #[test]
+    fn no_polar_periods_at_mid_latitudes() {
+        let coordinates = Coordinates::new(36.8065, 10.1815);
+
+        assert!(SolarTime::polar_periods(2022, coordinates).is_empty());
+    }
+
+    #[test]
+    fn solar_time_accessors() {
+        let coordinates = Coordinates::new(35.0 + 47.0 / 60.0, -78.0 - 39.0 / 60.0);
+        let date = Utc.ymd(2015, 7, 12).and_hms(0, 0, 0);
+        let solar = SolarTime::new(date, coordinates);
+
+        assert_eq!(solar.declination(), solar.solar.declination);
+        // Around mid-July the sun is about 22 degrees north,
+        // and the sundial is about 5.5 minutes late
+        assert!((solar.declination().degrees - 22.0).abs() < 0.1);
+        assert!((solar.equation_of_time() + 5.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn sun_position_at_transit() {
+        let london = Coordinates::new(51.5072, -0.1276);
+        let date = Utc.ymd(2022, 12, 21).and_hms(0, 0, 0);
+        let solar = SolarTime::new(date, london);
+        let transit = solar.transit.unwrap();
+        let (azimuth, altitude) = solar.position(transit);
+
+        // The sun is due south, at its highest altitude
+        assert!((azimuth.degrees - 180.0).abs() < 1.0, "{:?}", azimuth);
+        assert!((altitude.degrees - solar.transit_altitude()).abs() < 0.1);
+        for minutes in [-60, -10, 10, 60] {
+            let (_, other_altitude) = solar.position(transit + Duration::minutes(minutes));
+            assert!(other_altitude < altitude);
+        }
+    }
+
+    #[test]
+    fn sun_position_in_the_morning_and_afternoon() {
+        let london = Coordinates::new(51.5072, -0.1276);
+        let date = Utc.ymd(2022, 12, 21).and_hms(0, 0, 0);
+        let solar = SolarTime::new(date, london);
+
+        let (morning_azimuth, _) = solar.position(date.date().and_hms(10, 0, 0));
+        let (afternoon_azimuth, _) = solar.position(date.date().and_hms(14, 0, 0));
+        let (_, night_altitude) = solar.position(date.date().and_hms(23, 0, 0));
+
+        assert!(morning_azimuth.degrees > 90.0 && morning_azimuth.degrees < 180.0);
+        assert!(afternoon_azimuth.degrees > 180.0 && afternoon_azimuth.degrees < 270.0);
+        assert!(night_altitude.degrees < 0.0);
+    }
+
+    #[test]
+    fn shadow_ratio_at_asr() {
+        let coordinates = Coordinates::new(36.8065, 10.1815);
+        let solar = SolarTime::new(Utc.ymd(2022, 8, 1).and_hms(0, 0, 0), coordinates);
+        let noon_shadow_ratio = solar.shadow_ratio_at(solar.transit.unwrap());
+
+        // At Asr, the shadow equals the object (or twice) plus the noon shadow
+        for shadow_length in [1.0, 2.0] {
+            let asr = solar.afternoon(shadow_length);
+            let ratio = solar.shadow_ratio_at(asr) - noon_shadow_ratio;
+
+            assert!((ratio - shadow_length).abs() < 0.05, "{}", ratio);
+        }
+        assert_eq!(
+            solar.shadow_ratio_at(Utc.ymd(2022, 8, 1).and_hms(23, 0, 0)),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn solar_midnight_is_half_a_day_after_transit() {
+        let coordinates = Coordinates::new(35.0 + 47.0 / 60.0, -78.0 - 39.0 / 60.0);
+        let date = Utc.ymd(2015, 7, 12).and_hms(0, 0, 0);
+        let solar = SolarTime::new(date, coordinates);
+        let next_solar = SolarTime::new(date.tomorrow(), coordinates);
+        let midnight = solar.solar_midnight();
+
+        let since_transit = midnight.signed_duration_since(solar.transit.unwrap());
+        assert!((since_transit - Duration::hours(12)).num_seconds().abs() < 60);
+        assert!(midnight < next_solar.transit.unwrap());
+    }
+
+    #[test]
+    fn setting_hour_rounded_to_the_next_hour() {
+        let date = Utc.ymd(2022, 6, 21).and_hms(0, 0, 0);
+
+        assert_eq!(
+            SolarTime::setting_hour(4.0 + 59.0 / 60.0 + 45.0 / 3600.0, &date),
+            Some(Utc.ymd(2022, 6, 21).and_hms(5, 0, 0))
+        );
+        assert_eq!(
+            SolarTime::setting_hour(23.0 + 59.0 / 60.0 + 45.0 / 3600.0, &date),
+            Some(Utc.ymd(2022, 6, 22).and_hms(0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn calculate_time_for_unreachable_solar_angle() {
+        // In London, the sun does not go below 18 degrees in June
+        let coordinates = Coordinates::new(51.5074, -0.1278);
+        let date = Utc.ymd(2022, 6, 21).and_hms(0, 0, 0);
+        let solar = SolarTime::new(date, coordinates);
+
+        assert_eq!(
+            solar.checked_time_for_solar_angle(Angle::new(-18.0), false),
+            None
+        );
+        assert!(solar
+            .checked_time_for_solar_angle(Angle::new(-6.0), false)
+            .is_some());
+    }
+
+    #[test]
+    fn move_solar_time_to_another_date() {
+        let coordinates = Coordinates::new(35.0 + 47.0 / 60.0, -78.0 - 39.0 / 60.0);
+        let date = Utc.ymd(2015, 7, 12).and_hms(0, 0, 0);
+        let solar = SolarTime::new(date, coordinates).with_date(date.tomorrow());
+
+        assert_eq!(
+            solar.transit.unwrap(),
+            Utc.ymd(2015, 7, 13).and_hms(17, 20, 0)
+        );
+        assert_eq!(
+            solar.sunrise.unwrap(),
+            Utc.ymd(2015, 7, 13).and_hms(10, 8, 0)
+        );
+        assert_eq!(
+            solar.sunset.unwrap(),
+            Utc.ymd(2015, 7, 14).and_hms(00, 32, 0)
+        );
+        assert_eq!(
+            solar
+                .time_for_solar_angle(Angle::new(-6.0), false)
+                .format("%Y-%m-%d %-k:%M")
+                .to_string(),
+            "2015-07-13 9:38"
+        );
+    }
+
     #[test]
     fn calculate_time_for_solar_angle() {
         let coordinates = Coordinates::new(35.0 + 47.0 / 60.0, -78.0 - 39.0 / 60.0);
@@ -367,7 +877,8 @@ mod tests {
         let prev_solar = SolarCoordinates::new(yesterday.julian_day());
         let solar = SolarCoordinates::new(today.julian_day());
         let next_solar = SolarCoordinates::new(tomorrow.julian_day());
-        let solar_altitude = Angle::new(-50.0 / 60.0);
+        // Observers above sea level see the sun rise earlier and set later
+        let solar_altitude = Angle::new(-50.0 / 60.0) - coordinates.horizon_dip();
         let approx_transit = ops::approximate_transit(
             coordinates.longitude_angle(),
             solar.apparent_sidereal_time,
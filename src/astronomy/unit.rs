@@ -1,10 +1,22 @@
 use std::f64::consts::PI;
-use std::ops::{Add, Div, Mul, Sub};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+use std::str::FromStr;
 
 use crate::astronomy::ops;
+use crate::constants::EARTH_RADIUS_KM;
 use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike};
 
+/// Normalization of a value to the range [0, max)
 pub trait Normalize {
+    /// Wraps the value to the range [0, max)
+    ///
+    /// ```
+    /// use salati::prelude::*;
+    ///
+    /// assert_eq!(361.0.normalized_to_scale(360.0), 1.0);
+    /// assert_eq!((-45.0).normalized_to_scale(360.0), 315.0);
+    /// ```
     fn normalized_to_scale(&self, max: f64) -> f64;
 }
 
@@ -14,13 +26,38 @@ impl Normalize for f64 {
     }
 }
 
+/// Wraps the given degrees to the range [0, 360)
+///
+/// ```
+/// use salati::prelude::*;
+///
+/// assert_eq!(wrap_degrees(361.0), 1.0);
+/// assert_eq!(wrap_degrees(-45.0), 315.0);
+/// assert_eq!(wrap_degrees(720.0), 0.0);
+/// ```
+pub fn wrap_degrees(degrees: f64) -> f64 {
+    degrees.normalized_to_scale(360.0)
+}
+
 /// Convenience methods for the DateTime type.
 pub trait Stride {
     fn tomorrow(&self) -> Self;
     fn yesterday(&self) -> Self;
     fn julian_day(&self) -> f64;
     fn nearest_minute(&self) -> Self;
+    fn try_nearest_minute(&self) -> Option<Self>
+    where
+        Self: Sized;
+    fn nearest_minutes(&self, minutes: i64) -> Self;
+    fn try_nearest_minutes(&self, minutes: i64) -> Option<Self>
+    where
+        Self: Sized;
+    fn round_up_minute(&self) -> Self;
+    fn round_down_minute(&self) -> Self;
     fn adjust_time(&self, minutes: i64) -> Self;
+    fn try_adjust_time(&self, minutes: i64) -> Option<Self>
+    where
+        Self: Sized;
     fn next_date(&self, fwd: bool) -> Self;
 }
 
@@ -45,22 +82,91 @@ impl<Tz: TimeZone> Stride for DateTime<Tz> {
         )
     }
 
+    /// Returns the date/time rounded to the nearest minute.
+    ///
+    /// Panics when rounding up goes beyond the latest supported date,
+    /// see [try_nearest_minute](#tymethod.try_nearest_minute).
     fn nearest_minute(&self) -> Self {
-        let adjusted = self.clone();
-        let seconds = adjusted.second() as i64;
+        self.try_nearest_minute()
+            .expect("Rounded date/time is out of range.")
+    }
+
+    /// Returns the date/time rounded to the nearest minute,
+    /// or `None` when it's beyond the latest supported date.
+    fn try_nearest_minute(&self) -> Option<Self> {
+        self.try_nearest_minutes(1)
+    }
+
+    /// Returns the date/time rounded to the nearest multiple of the given minutes
+    /// since midnight, e.g. 04:37 is rounded to 04:35 for 5 minutes.
+    ///
+    /// Panics when `minutes` is not positive or when rounding up goes beyond
+    /// the latest supported date, see [try_nearest_minutes](#tymethod.try_nearest_minutes).
+    fn nearest_minutes(&self, minutes: i64) -> Self {
+        self.try_nearest_minutes(minutes)
+            .expect("Rounded date/time is out of range.")
+    }
+
+    /// Returns the date/time rounded to the nearest multiple of the given minutes
+    /// since midnight, or `None` when `minutes` is not positive
+    /// or when it's beyond the latest supported date.
+    fn try_nearest_minutes(&self, minutes: i64) -> Option<Self> {
+        if minutes <= 0 {
+            return None;
+        }
+
+        // Durations are limited to i64::MAX milliseconds
+        let step = minutes
+            .checked_mul(60)
+            .filter(|step| *step <= i64::MAX / 1000)?;
+        let minute_of_day = self.hour() as i64 * 60 + self.minute() as i64;
+        let seconds = (minute_of_day % minutes) * 60 + self.second() as i64;
 
-        if adjusted.second() >= 30 {
-            adjusted + Duration::seconds(60 - seconds)
+        if seconds * 2 >= step {
+            self.clone()
+                .checked_add_signed(Duration::seconds(step - seconds))
         } else {
-            adjusted + Duration::seconds(-seconds)
+            self.clone().checked_add_signed(Duration::seconds(-seconds))
         }
     }
 
+    fn round_up_minute(&self) -> Self {
+        let adjusted = self.round_down_minute();
+
+        if adjusted == *self {
+            adjusted
+        } else {
+            adjusted + Duration::minutes(1)
+        }
+    }
+
+    fn round_down_minute(&self) -> Self {
+        let adjusted = self.clone();
+        let seconds = adjusted.second() as i64;
+        let nanoseconds = adjusted.nanosecond() as i64;
+
+        adjusted - Duration::seconds(seconds) - Duration::nanoseconds(nanoseconds)
+    }
+
+    /// Returns the date/time shifted by the given minutes.
+    ///
+    /// Panics when the result is out of the supported dates,
+    /// see [try_adjust_time](#tymethod.try_adjust_time).
     fn adjust_time(&self, minutes: i64) -> Self {
-        let some_date = self.clone();
-        some_date
-            .checked_add_signed(Duration::seconds(minutes * 60))
-            .unwrap()
+        self.try_adjust_time(minutes)
+            .expect("Adjusted date/time is out of range.")
+    }
+
+    /// Returns the date/time shifted by the given minutes,
+    /// or `None` when the result is out of the supported dates.
+    fn try_adjust_time(&self, minutes: i64) -> Option<Self> {
+        // Durations are limited to i64::MAX milliseconds
+        let max_seconds = i64::MAX / 1000;
+        let seconds = minutes
+            .checked_mul(60)
+            .filter(|seconds| (-max_seconds..=max_seconds).contains(seconds))?;
+
+        self.clone().checked_add_signed(Duration::seconds(seconds))
     }
 
     fn next_date(&self, fwd: bool) -> Self {
@@ -91,7 +197,8 @@ impl<Tz: TimeZone> Stride for DateTime<Tz> {
     }
 }
 
-#[derive(PartialEq, Debug, Copy, Clone)]
+#[derive(PartialEq, PartialOrd, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Angle {
     pub degrees: f64,
 }
@@ -101,6 +208,30 @@ impl Angle {
         Angle { degrees: value }
     }
 
+    /// Returns the angle given in degrees, minutes and seconds,
+    /// the sign of degrees applies to the whole angle, including `-0.0`
+    /// for angles between -1° and 0° (e.g. `from_dms(-0.0, 7, 40.0)`).
+    pub fn from_dms(degrees: f64, minutes: u32, seconds: f64) -> Self {
+        let value = degrees.abs() + minutes as f64 / 60.0 + seconds / 3600.0;
+
+        if degrees.is_sign_negative() {
+            Angle::new(-value)
+        } else {
+            Angle::new(value)
+        }
+    }
+
+    /// Returns the angle as (degrees, minutes, seconds), the sign is carried
+    /// by the whole degrees, which are `-0.0` for angles between -1° and 0°.
+    pub fn to_dms(&self) -> (f64, u32, f64) {
+        let value = self.degrees.abs();
+        let degrees = value.trunc();
+        let minutes = ((value - degrees) * 60.0).trunc();
+        let seconds = (value - degrees - minutes / 60.0) * 3600.0;
+
+        (degrees.copysign(self.degrees), minutes as u32, seconds)
+    }
+
     pub fn from_radians(value: f64) -> Self {
         Angle {
             degrees: (value * 180.0) / PI,
@@ -111,6 +242,35 @@ impl Angle {
         (self.degrees * PI) / 180.0
     }
 
+    /// Returns the smallest of the two angles (compared by raw degrees)
+    pub fn min(self, other: Angle) -> Angle {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the largest of the two angles (compared by raw degrees)
+    pub fn max(self, other: Angle) -> Angle {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Divides the degrees of both angles, returns `None` when the divisor is zero
+    pub fn checked_div(&self, rhs: Angle) -> Option<Angle> {
+        if rhs.degrees == 0.0 {
+            return None;
+        }
+
+        Some(Angle {
+            degrees: self.degrees / rhs.degrees,
+        })
+    }
+
     pub fn unwound(&self) -> Angle {
         Angle {
             degrees: self.degrees.normalized_to_scale(360.0),
@@ -149,6 +309,20 @@ impl Sub for Angle {
     }
 }
 
+impl AddAssign for Angle {
+    fn add_assign(&mut self, rhs: Angle) {
+        self.degrees += rhs.degrees;
+    }
+}
+
+impl SubAssign for Angle {
+    fn sub_assign(&mut self, rhs: Angle) {
+        self.degrees -= rhs.degrees;
+    }
+}
+
+/// Multiplies the degrees of both angles, which is rarely meaningful,
+/// prefer scaling an angle by a scalar (e.g. `angle * 0.5`).
 impl Mul for Angle {
     type Output = Angle;
 
@@ -159,33 +333,94 @@ impl Mul for Angle {
     }
 }
 
-impl Div for Angle {
+/// Scales the angle by the given factor
+impl Mul<f64> for Angle {
     type Output = Angle;
 
-    fn div(self, rhs: Angle) -> Angle {
-        if rhs.degrees == 0.0 {
-            panic!("Cannot divide by zero.");
-        }
-
+    fn mul(self, rhs: f64) -> Angle {
         Angle {
-            degrees: self.degrees / rhs.degrees,
+            degrees: self.degrees * rhs,
         }
     }
 }
 
+/// Divides the degrees of both angles
+///
+/// # Panics
+///
+/// Panics if the divisor is zero, see [checked_div](struct.Angle.html#method.checked_div)
+impl Div for Angle {
+    type Output = Angle;
+
+    fn div(self, rhs: Angle) -> Angle {
+        self.checked_div(rhs).expect("Cannot divide by zero.")
+    }
+}
+
 /// The latitude and longitude associated with a location.
 /// Both latiude and longitude values are specified in degrees.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coordinates {
     pub latitude: f64,
     pub longitude: f64,
+    /// Elevation of the observer above sea level, used to correct sunrise and sunset
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub elevation_meters: f64,
 }
 
 impl Coordinates {
+    /// Returns the coordinates as given, out of range values are kept,
+    /// see [try_new](#method.try_new) and [normalized](#method.normalized).
     pub fn new(latitude: f64, longitude: f64) -> Self {
+        Coordinates::with_elevation(latitude, longitude, 0.0)
+    }
+
+    /// Returns the coordinates if the latitude is within [-90, 90]
+    /// and the longitude is within [-180, 180]
+    pub fn try_new(latitude: f64, longitude: f64) -> Result<Self, CoordinateError> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(CoordinateError::LatitudeOutOfRange(latitude));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(CoordinateError::LongitudeOutOfRange(longitude));
+        }
+
+        Ok(Coordinates::new(latitude, longitude))
+    }
+
+    /// Returns the coordinates given as (degrees, minutes, seconds) for both
+    /// latitude and longitude, southern and western values have negative degrees,
+    /// see [Angle::from_dms].
+    pub fn from_dms(latitude: (f64, u32, f64), longitude: (f64, u32, f64)) -> Self {
+        Coordinates::new(
+            Angle::from_dms(latitude.0, latitude.1, latitude.2).degrees,
+            Angle::from_dms(longitude.0, longitude.1, longitude.2).degrees,
+        )
+    }
+
+    pub fn with_elevation(latitude: f64, longitude: f64, elevation_meters: f64) -> Self {
         Coordinates {
             latitude,
             longitude,
+            elevation_meters,
+        }
+    }
+
+    /// Returns the coordinates with the longitude wrapped to [-180, 180]
+    /// (e.g. 200° is -160°), and the latitude clamped to [-90, 90].
+    /// Coordinates within these ranges are left as is.
+    pub fn normalized(&self) -> Coordinates {
+        let longitude = if (-180.0..=180.0).contains(&self.longitude) {
+            self.longitude
+        } else {
+            (self.longitude + 180.0).normalized_to_scale(360.0) - 180.0
+        };
+
+        Coordinates {
+            latitude: self.latitude.clamp(-90.0, 90.0),
+            longitude,
+            elevation_meters: self.elevation_meters,
         }
     }
 }
@@ -198,6 +433,107 @@ impl Coordinates {
     pub fn longitude_angle(&self) -> Angle {
         Angle::new(self.longitude)
     }
+
+    /// Returns the dip of the horizon seen by the observer due to its elevation
+    pub fn horizon_dip(&self) -> Angle {
+        Angle::new(0.0347 * self.elevation_meters.max(0.0).sqrt())
+    }
+
+    /// Returns the great-circle distance, in kilometers, to the given coordinates
+    pub fn distance_to(&self, other: Coordinates) -> f64 {
+        // Haversine formula
+        let delta_latitude = (other.latitude_angle() - self.latitude_angle()).radians();
+        let delta_longitude = (other.longitude_angle() - self.longitude_angle()).radians();
+        let term1 = (delta_latitude / 2.0).sin().powi(2)
+            + self.latitude_angle().radians().cos()
+                * other.latitude_angle().radians().cos()
+                * (delta_longitude / 2.0).sin().powi(2);
+        let term2 = 2.0 * term1.sqrt().asin();
+
+        EARTH_RADIUS_KM * term2
+    }
+}
+
+/// Error returned when the coordinates are out of range
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum CoordinateError {
+    /// The latitude is not within [-90, 90]
+    LatitudeOutOfRange(f64),
+    /// The longitude is not within [-180, 180]
+    LongitudeOutOfRange(f64),
+}
+
+impl fmt::Display for CoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoordinateError::LatitudeOutOfRange(latitude) => {
+                write!(f, "Latitude {} is out of range [-90, 90]", latitude)
+            }
+            CoordinateError::LongitudeOutOfRange(longitude) => {
+                write!(f, "Longitude {} is out of range [-180, 180]", longitude)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoordinateError {}
+
+/// Error returned when parsing coordinates from a string
+#[derive(PartialEq, Debug, Clone)]
+pub enum ParseCoordinatesError {
+    /// The input is not formatted as "latitude,longitude"
+    Malformed(String),
+    /// The latitude is not within [-90, 90]
+    LatitudeOutOfRange(f64),
+    /// The longitude is not within [-180, 180]
+    LongitudeOutOfRange(f64),
+}
+
+impl fmt::Display for ParseCoordinatesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCoordinatesError::Malformed(value) => write!(
+                f,
+                "Invalid coordinates '{}', expected 'latitude,longitude'",
+                value
+            ),
+            ParseCoordinatesError::LatitudeOutOfRange(latitude) => {
+                write!(f, "Latitude {} is out of range [-90, 90]", latitude)
+            }
+            ParseCoordinatesError::LongitudeOutOfRange(longitude) => {
+                write!(f, "Longitude {} is out of range [-180, 180]", longitude)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseCoordinatesError {}
+
+impl From<CoordinateError> for ParseCoordinatesError {
+    fn from(error: CoordinateError) -> Self {
+        match error {
+            CoordinateError::LatitudeOutOfRange(latitude) => {
+                ParseCoordinatesError::LatitudeOutOfRange(latitude)
+            }
+            CoordinateError::LongitudeOutOfRange(longitude) => {
+                ParseCoordinatesError::LongitudeOutOfRange(longitude)
+            }
+        }
+    }
+}
+
+impl FromStr for Coordinates {
+    type Err = ParseCoordinatesError;
+
+    /// Parses coordinates given as "latitude,longitude"
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let malformed = || ParseCoordinatesError::Malformed(String::from(value));
+        let (latitude, longitude) = value.split_once(',').ok_or_else(malformed)?;
+        let latitude: f64 = latitude.trim().parse().map_err(|_| malformed())?;
+        let longitude: f64 = longitude.trim().parse().map_err(|_| malformed())?;
+
+        Coordinates::try_new(latitude, longitude).map_err(ParseCoordinatesError::from)
+    }
 }
 
 #[cfg(test)]
@@ -218,6 +554,100 @@ mod tests {
         assert_eq!(Angle::new(90.0).radians(), PI / 2.0);
     }
 
+    #[test]
+    fn angle_from_dms() {
+        assert!((Angle::from_dms(36.0, 48, 23.4).degrees - 36.8065).abs() < 1e-9);
+        assert!((Angle::from_dms(-34.0, 36, 13.0).degrees + 34.603_611).abs() < 1e-6);
+        assert_eq!(Angle::from_dms(45.0, 30, 0.0), Angle::new(45.5));
+        assert_eq!(Angle::from_dms(-0.0, 30, 0.0), Angle::new(-0.5));
+    }
+
+    #[test]
+    fn angle_dms_round_trip() {
+        for (degrees, minutes, seconds) in [
+            (36.0, 48, 23.4),
+            (-58.0, 22, 54.0),
+            (0.0, 0, 1.5),
+            (-0.0, 7, 40.0),
+        ] {
+            let (d, m, s) = Angle::from_dms(degrees, minutes, seconds).to_dms();
+
+            assert_eq!(d, degrees);
+            assert_eq!(d.is_sign_negative(), degrees.is_sign_negative());
+            assert_eq!(m, minutes);
+            assert!((s - seconds).abs() < 1e-6);
+        }
+
+        let (d, m, s) = Angle::new(-0.5).to_dms();
+        assert!(d == 0.0 && d.is_sign_negative());
+        assert_eq!((m, s), (30, 0.0));
+    }
+
+    #[test]
+    fn coordinates_from_dms() {
+        let buenos_aires = Coordinates::from_dms((-34.0, 36, 13.0), (-58.0, 22, 54.0));
+
+        assert!((buenos_aires.latitude + 34.603_611).abs() < 1e-6);
+        assert!((buenos_aires.longitude + 58.381_667).abs() < 1e-6);
+
+        let london = Coordinates::from_dms((51.0, 30, 26.0), (-0.0, 7, 40.0));
+        assert!((london.longitude + 0.127_778).abs() < 1e-6);
+    }
+
+    #[test]
+    fn compare_angles() {
+        assert!(Angle::new(10.0) < Angle::new(20.0));
+        assert!(Angle::new(20.0) > Angle::new(10.0));
+        assert!(Angle::new(-18.0) < Angle::new(-12.0));
+        assert!(Angle::new(-0.5) > Angle::new(-0.8333));
+        assert!(Angle::new(15.0) <= Angle::new(15.0));
+        assert!(Angle::new(15.0) >= Angle::new(15.0));
+    }
+
+    #[test]
+    fn min_and_max_angles() {
+        let fajr = Angle::new(-18.0);
+        let isha = Angle::new(-17.0);
+
+        assert_eq!(fajr.min(isha), fajr);
+        assert_eq!(fajr.max(isha), isha);
+        assert_eq!(isha.min(isha), isha);
+        assert_eq!(Angle::new(350.0).max(Angle::new(10.0)), Angle::new(350.0));
+    }
+
+    #[test]
+    fn angle_scalar_multiplication() {
+        assert_eq!(Angle::new(45.0) * 0.5, Angle { degrees: 22.5 });
+        assert_eq!(Angle::new(-10.0) * 3.0, Angle::new(-30.0));
+    }
+
+    #[test]
+    fn angle_checked_division() {
+        assert_eq!(
+            Angle::new(90.0).checked_div(Angle::new(2.0)),
+            Some(Angle::new(45.0))
+        );
+        assert_eq!(Angle::new(90.0).checked_div(Angle::new(0.0)), None);
+        assert_eq!(Angle::new(90.0) / Angle::new(2.0), Angle::new(45.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot divide by zero.")]
+    fn angle_division_by_zero() {
+        let _ = Angle::new(90.0) / Angle::new(0.0);
+    }
+
+    #[test]
+    fn angle_add_and_sub_assign() {
+        let mut angle = Angle::new(45.0);
+
+        angle += Angle::new(15.0);
+        assert_eq!(angle, Angle::new(60.0));
+
+        angle -= Angle::new(90.0);
+        assert_eq!(angle, Angle::new(-30.0));
+    }
+
     #[test]
     fn normalize_value() {
         assert_eq!(2.0_f64.normalized_to_scale(-5.0), -3.0);
@@ -264,6 +694,38 @@ mod tests {
         assert_eq!((angle_a + angle_b).degrees, 90.0)
     }
 
+    #[test]
+    fn distance_between_coordinates() {
+        let london = Coordinates::new(51.5074, -0.1278);
+        let paris = Coordinates::new(48.8566, 2.3522);
+
+        assert!((london.distance_to(paris) - 343.5).abs() < 1.0);
+        assert!((paris.distance_to(london) - 343.5).abs() < 1.0);
+        assert_eq!(london.distance_to(london), 0.0);
+    }
+
+    #[test]
+    fn normalize_coordinates() {
+        let coordinates = Coordinates::new;
+
+        assert_eq!(
+            coordinates(36.8, 200.0).normalized(),
+            coordinates(36.8, -160.0)
+        );
+        assert_eq!(
+            coordinates(36.8, -190.0).normalized(),
+            coordinates(36.8, 170.0)
+        );
+        assert_eq!(
+            coordinates(95.0, 540.0).normalized(),
+            coordinates(90.0, -180.0)
+        );
+        assert_eq!(
+            coordinates(-36.8, -180.0).normalized(),
+            coordinates(-36.8, -180.0)
+        );
+    }
+
     #[test]
     fn calculate_nearest_minute() {
         let time_1 = Utc.ymd(2015, 7, 13).and_hms(4, 37, 30);
@@ -279,6 +741,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nearest_minute_near_the_latest_date() {
+        let latest = Utc.ymd(262143, 12, 31).and_hms(23, 59, 45);
+
+        assert_eq!(latest.try_nearest_minute(), None);
+        assert_eq!(
+            Utc.ymd(262143, 12, 31)
+                .and_hms(23, 59, 15)
+                .try_nearest_minute(),
+            Some(Utc.ymd(262143, 12, 31).and_hms(23, 59, 0))
+        );
+    }
+
+    #[test]
+    fn nearest_multiple_of_minutes() {
+        let time = Utc.ymd(2022, 8, 1).and_hms(4, 37, 29);
+
+        assert_eq!(time.nearest_minutes(1), time.nearest_minute());
+        assert_eq!(
+            time.nearest_minutes(5),
+            Utc.ymd(2022, 8, 1).and_hms(4, 35, 0)
+        );
+        assert_eq!(
+            Utc.ymd(2022, 8, 1).and_hms(4, 37, 30).nearest_minutes(5),
+            Utc.ymd(2022, 8, 1).and_hms(4, 40, 0)
+        );
+        assert_eq!(
+            Utc.ymd(2022, 8, 1).and_hms(23, 55, 0).nearest_minutes(15),
+            Utc.ymd(2022, 8, 2).and_hms(0, 0, 0)
+        );
+
+        let latest = Utc.ymd(262143, 12, 31).and_hms(23, 58, 0);
+        assert_eq!(latest.try_nearest_minutes(5), None);
+        assert_eq!(time.try_nearest_minutes(0), None);
+        assert_eq!(time.try_nearest_minutes(-5), None);
+    }
+
+    #[test]
+    fn adjust_time_out_of_range() {
+        let time = Utc.ymd(2015, 7, 13).and_hms(4, 37, 0);
+        let latest = Utc.ymd(262143, 12, 31).and_hms(23, 30, 0);
+
+        assert_eq!(time.try_adjust_time(-37), Some(time.adjust_time(-37)));
+        assert_eq!(
+            latest.try_adjust_time(29),
+            Some(Utc.ymd(262143, 12, 31).and_hms(23, 59, 0))
+        );
+        assert_eq!(latest.try_adjust_time(30), None);
+        assert_eq!(time.try_adjust_time(i64::MAX), None);
+        assert_eq!(time.try_adjust_time(i64::MIN), None);
+        assert_eq!(time.try_adjust_time(i64::MAX / 60), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Adjusted date/time is out of range.")]
+    fn adjust_time_panics_out_of_range() {
+        Utc.ymd(2015, 7, 13).and_hms(4, 37, 0).adjust_time(i64::MAX);
+    }
+
+    #[test]
+    fn round_up_and_down_to_minute() {
+        let time_1 = Utc.ymd(2015, 7, 13).and_hms(4, 37, 29);
+        let time_2 = Utc.ymd(2015, 7, 13).and_hms(4, 37, 0);
+
+        assert_eq!(
+            time_1.round_up_minute(),
+            Utc.ymd(2015, 7, 13).and_hms(4, 38, 0)
+        );
+        assert_eq!(
+            time_1.round_down_minute(),
+            Utc.ymd(2015, 7, 13).and_hms(4, 37, 0)
+        );
+        assert_eq!(time_2.round_up_minute(), time_2);
+        assert_eq!(time_2.round_down_minute(), time_2);
+    }
+
     macro_rules! tomorrow_tests {
         ($($name:ident: $value:expr,)*) => {
         $(
@@ -325,4 +863,83 @@ mod tests {
         test_yesterday_first_day_year_2: (Utc.ymd(1500, 1, 1), Utc.ymd(1499, 12, 31)),
         test_yesterday_first_day_year_3: (Utc.ymd(1501, 1, 1), Utc.ymd(1500, 12, 31)),
     }
+
+    #[test]
+    fn horizon_dip_at_elevation() {
+        assert_eq!(Coordinates::new(21.0, 39.0).horizon_dip().degrees, 0.0);
+        let dip = Coordinates::with_elevation(21.0, 39.0, 100.0).horizon_dip();
+        assert!((dip.degrees - 0.347).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_coordinates() {
+        assert_eq!(
+            "51.5072,0.1276".parse::<Coordinates>(),
+            Ok(Coordinates::new(51.5072, 0.1276))
+        );
+        assert_eq!(
+            " 36.8065 , 10.1815 ".parse::<Coordinates>(),
+            Ok(Coordinates::new(36.8065, 10.1815))
+        );
+    }
+
+    #[test]
+    fn parse_malformed_coordinates() {
+        assert_eq!(
+            "51.5072 0.1276".parse::<Coordinates>(),
+            Err(ParseCoordinatesError::Malformed(String::from(
+                "51.5072 0.1276"
+            )))
+        );
+        assert!("north,0.1276".parse::<Coordinates>().is_err());
+        assert!("51.5072,0.1276,3".parse::<Coordinates>().is_err());
+    }
+
+    #[test]
+    fn parse_out_of_range_coordinates() {
+        assert_eq!(
+            "91.0,0.1276".parse::<Coordinates>(),
+            Err(ParseCoordinatesError::LatitudeOutOfRange(91.0))
+        );
+        assert_eq!(
+            "51.5072,-180.5".parse::<Coordinates>(),
+            Err(ParseCoordinatesError::LongitudeOutOfRange(-180.5))
+        );
+    }
+
+    #[test]
+    fn try_new_coordinates_within_range() {
+        assert_eq!(
+            Coordinates::try_new(90.0, 180.0),
+            Ok(Coordinates::new(90.0, 180.0))
+        );
+        assert_eq!(
+            Coordinates::try_new(-90.0, -180.0),
+            Ok(Coordinates::new(-90.0, -180.0))
+        );
+    }
+
+    #[test]
+    fn try_new_coordinates_out_of_range() {
+        assert_eq!(
+            Coordinates::try_new(510.0, 0.1276),
+            Err(CoordinateError::LatitudeOutOfRange(510.0))
+        );
+        assert_eq!(
+            Coordinates::try_new(-90.5, 0.1276),
+            Err(CoordinateError::LatitudeOutOfRange(-90.5))
+        );
+        assert_eq!(
+            Coordinates::try_new(51.5072, 180.1),
+            Err(CoordinateError::LongitudeOutOfRange(180.1))
+        );
+    }
+
+    #[test]
+    fn new_coordinates_out_of_range() {
+        let coordinates = Coordinates::new(510.0, 200.0);
+
+        assert_eq!(coordinates.latitude, 510.0);
+        assert_eq!(coordinates.longitude, 200.0);
+    }
 }
@@ -5,18 +5,86 @@
 //! ## Example
 //!
 //! salati -c "51.5072,0.1276" --method karachi
+//! salati -c "51.5072,0.1276" --method karachi --format json
+//! salati -c "51.5072,0.1276" --method karachi --timezone Europe/London
+//! salati -c "51.5072,0.1276" --method karachi --date 2022-08-01
+//! salati -c "51.5072,0.1276" --method karachi --prayer fajr
+//! salati -c "51.5072,0.1276" --method karachi --next
+//! salati -c "51.5072,0.1276" --qibla
+//! salati --list-methods
+//! salati --config salati.toml
 //!
-use clap::Parser;
+use chrono::FixedOffset;
+#[cfg(feature = "chrono-tz")]
+use chrono::Offset;
+use clap::{Parser, ValueEnum};
 
 use salati::prelude::*;
 
+#[derive(PartialEq, Debug, Copy, Clone, ValueEnum)]
+enum Format {
+    Text,
+    /// Requires the `serde` feature
+    Json,
+    Csv,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::Text
+    }
+}
+
+#[derive(PartialEq, Debug, Copy, Clone, ValueEnum)]
+enum TimeFormat {
+    #[clap(name = "12")]
+    TwelveHour,
+    #[clap(name = "24")]
+    TwentyFourHour,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        TimeFormat::TwentyFourHour
+    }
+}
+
+impl TimeFormat {
+    /// Formats the time to the minute, seconds kept by `Rounding::None` are rounded
+    /// the same way as the default rounding instead of being truncated
+    fn format<Tz: TimeZone>(&self, datetime: DateTime<Tz>) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        let datetime = datetime.nearest_minute();
+
+        match self {
+            TimeFormat::TwelveHour => datetime.format("%I:%M %p").to_string(),
+            TimeFormat::TwentyFourHour => datetime.format("%H:%M").to_string(),
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)] // Read from `Cargo.toml`
 struct Cli {
-    #[clap(short, long)]
-    coordinates: String,
-    #[clap(long, arg_enum)]
-    method: Method,
+    #[clap(short, long, required_unless_present_any = ["list-methods", "config"])]
+    coordinates: Option<String>,
+    #[clap(long, arg_enum, required_unless_present_any = ["list-methods", "config", "qibla"])]
+    method: Option<Method>,
+    #[clap(
+        long,
+        conflicts_with_all = &[
+            "coordinates",
+            "method",
+            "madhab",
+            "twilight",
+            "high-latitude-rule",
+            "polar-circle-resolution",
+        ]
+    )]
+    /// Configuration file (TOML or JSON) of the location and parameters, requires the `serde` feature
+    config: Option<String>,
     #[clap(long, arg_enum, default_value_t=Madhab::default())]
     madhab: Madhab,
     #[clap(long, arg_enum, default_value_t=Twilight::default())]
@@ -26,40 +94,477 @@ struct Cli {
     high_latitude_rule: Option<HighLatitudeRule>,
     #[clap(long, arg_enum, default_value_t=PolarCircleResolution::default())]
     polar_circle_resolution: PolarCircleResolution,
+    #[clap(long, arg_enum, default_value_t=Format::default())]
+    format: Format,
+    #[clap(long, arg_enum, default_value_t=TimeFormat::default())]
+    /// Clock used to print times in the text format
+    time_format: TimeFormat,
+    #[clap(long, value_parser = parse_date)]
+    /// Date (YYYY-MM-DD) of the prayer times, today by default
+    date: Option<Date<Utc>>,
+    #[clap(long, arg_enum)]
+    /// Print only the time of the given prayer
+    prayer: Option<Prayer>,
+    #[clap(long, conflicts_with_all = &["date", "prayer", "format"])]
+    /// Print the next prayer and the time remaining until it as text, then exit
+    next: bool,
+    #[clap(long)]
+    /// Print the qibla direction and the distance to the Kaaba (of the coordinates
+    /// or the configuration file), then exit
+    qibla: bool,
+    #[clap(long, exclusive = true)]
+    /// Print the supported calculation methods and exit
+    list_methods: bool,
+    #[cfg(feature = "chrono-tz")]
+    #[clap(long)]
+    /// IANA timezone name (e.g. Africa/Tunis) used to print local times, UTC by default
+    timezone: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+fn load_config(path: &str) -> (Coordinates, Parameters) {
+    let exit = |error: &dyn std::fmt::Display| -> ! {
+        eprintln!("{}: {}", path, error);
+        std::process::exit(1);
+    };
+
+    let content = std::fs::read_to_string(path).unwrap_or_else(|error| exit(&error));
+    let config: Config = if path.ends_with(".json") {
+        serde_json::from_str(&content).unwrap_or_else(|error| exit(&error))
+    } else {
+        toml::from_str(&content).unwrap_or_else(|error| exit(&error))
+    };
+
+    config
+        .into_parameters()
+        .unwrap_or_else(|error| exit(&error))
+}
+
+#[cfg(not(feature = "serde"))]
+fn load_config(_path: &str) -> (Coordinates, Parameters) {
+    eprintln!(
+        "Configuration files are not available, please build salati with the `serde` feature"
+    );
+    std::process::exit(1);
+}
+
+/// Returns the coordinates given as command line arguments
+fn coordinates_from_args(args: &Cli) -> Coordinates {
+    // Coordinates are required, unless listing the methods or using a config file
+    match args
+        .coordinates
+        .clone()
+        .unwrap_or_default()
+        .parse::<Coordinates>()
+    {
+        Ok(coordinates) => coordinates,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Returns the coordinates and parameters given as command line arguments
+fn parameters_from_args(args: &Cli) -> (Coordinates, Parameters) {
+    let coordinates = coordinates_from_args(args);
+    let high_latitude_rule = args
+        .high_latitude_rule
+        .unwrap_or_else(|| HighLatitudeRule::recommended(coordinates));
+
+    // The method is required, unless listing the methods, using a config file or the qibla
+    let params = ParametersBuilder::from_method(args.method.unwrap(), args.madhab)
+        .twilight(args.twilight)
+        .polar_circle_resolution(args.polar_circle_resolution)
+        .high_latitude_rule(high_latitude_rule)
+        .build();
+
+    (coordinates, params)
+}
+
+fn print_methods() {
+    for method in Method::value_variants() {
+        let name = method.to_possible_value().unwrap().get_name().to_string();
+        println!("{}", format_method(&name, &method.parameters()));
+    }
+}
+
+fn format_method(name: &str, params: &Parameters) -> String {
+    let fajr = if params.fajr_interval > 0 {
+        format!("{} minutes before sunrise", params.fajr_interval)
+    } else {
+        format!("{}°", params.fajr_angle)
+    };
+    let isha = if params.isha_interval > 0 {
+        format!("{} minutes after maghrib", params.isha_interval)
+    } else {
+        format!("{}°", params.isha_angle)
+    };
+
+    format!("{:<24} fajr: {}, isha: {}", name, fajr, isha)
+}
+
+fn format_qibla(qiblah: &Qiblah) -> String {
+    format!(
+        "Qibla: {:.1}° ({}), distance to the Kaaba: {:.0} km",
+        qiblah.direction().degrees,
+        qiblah.cardinal(),
+        qiblah.distance()
+    )
+}
+
+/// Returns the prayer following the given time, its time and the time remaining until it,
+/// after Isha and the night prayers, the next one is the following day's Fajr
+fn next_prayer(
+    prayers: &PrayerTimes,
+    now: DateTime<Utc>,
+) -> Option<(Prayer, DateTime<Utc>, Duration)> {
+    let (prayer, time) = prayers.upcoming(now, 1).into_iter().next()?;
+
+    Some((prayer, time, time.signed_duration_since(now)))
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    // Round to the nearest minute, like PrayerTimes::time_remaining_at
+    let minutes = (remaining.num_seconds() + 30) / 60;
+
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Returns true if the given prayer should be printed
+fn is_selected(prayer: Prayer, selected: Option<Prayer>) -> bool {
+    selected.map_or(true, |selected| selected == prayer)
+}
+
+fn parse_date(value: &str) -> Result<Date<Utc>, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| Utc.from_utc_date(&date))
+        .map_err(|_| format!("invalid date `{}`, expected YYYY-MM-DD", value))
+}
+
+#[cfg(feature = "chrono-tz")]
+fn parse_timezone(args: &Cli) -> Option<chrono_tz::Tz> {
+    args.timezone.as_ref().map(|name| match name.parse() {
+        Ok(timezone) => timezone,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    })
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct Schedule<'a> {
+    imsak: &'a PrayerTime,
+    fajr: &'a PrayerTime,
+    sunrise: &'a PrayerTime,
+    duha: &'a PrayerTime,
+    dhuhr: &'a PrayerTime,
+    asr: &'a PrayerTime,
+    maghrib: &'a PrayerTime,
+    isha: &'a PrayerTime,
+    middle_of_the_night: &'a PrayerTime,
+    qiyam: &'a PrayerTime,
+}
+
+#[cfg(feature = "serde")]
+fn print_json(prayers: &PrayerTimes, selected: Option<Prayer>) {
+    if let Some(prayer) = selected {
+        let prayer_time = prayers.prayer_time(prayer);
+        println!("{}", serde_json::to_string_pretty(&prayer_time).unwrap());
+        return;
+    }
+
+    let schedule = Schedule {
+        imsak: &prayers.imsak,
+        fajr: &prayers.fajr,
+        sunrise: &prayers.sunrise,
+        duha: &prayers.duha,
+        dhuhr: &prayers.dhuhr,
+        asr: &prayers.asr,
+        maghrib: &prayers.maghrib,
+        isha: &prayers.isha,
+        middle_of_the_night: &prayers.middle_of_the_night,
+        qiyam: &prayers.qiyam,
+    };
+    println!("{}", serde_json::to_string_pretty(&schedule).unwrap());
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_json(_prayers: &PrayerTimes, _selected: Option<Prayer>) {
+    eprintln!("JSON output is not available, please build salati with the `serde` feature");
+    std::process::exit(1);
 }
 
 pub fn main() {
     let args = Cli::parse();
 
-    println!(
-        "Using coordinates: {}, method: {:?}\n",
-        args.coordinates, args.method
-    );
+    if args.list_methods {
+        print_methods();
+        return;
+    }
+
+    if args.qibla {
+        let coordinates = match &args.config {
+            Some(path) => load_config(path).0,
+            None => coordinates_from_args(&args),
+        };
+        let qiblah = Qiblah::new(coordinates);
+        println!("{}", format_qibla(&qiblah));
+        return;
+    }
+
+    let (coordinates, params, method) = match &args.config {
+        Some(path) => {
+            let (coordinates, params) = load_config(path);
+            (coordinates, params, params.method)
+        }
+        None => {
+            let (coordinates, params) = parameters_from_args(&args);
+            (coordinates, params, args.method.unwrap())
+        }
+    };
 
-    let parts: Vec<&str> = args.coordinates.split(',').collect();
-    let long: f64 = parts[0].parse().unwrap();
-    let lat: f64 = parts[1].parse().unwrap();
-    let coordinates = Coordinates::new(long, lat);
+    let date = args.date.unwrap_or_else(Utc::today);
 
-    let date = Utc::today();
-    let mut params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
-    params.twilight = args.twilight;
-    params.polar_circle_resolution = args.polar_circle_resolution;
-    match args.high_latitude_rule {
-        Some(rule) => params.high_latitude_rule = rule,
-        None => params.high_latitude_rule = HighLatitudeRule::recommended(coordinates),
+    #[cfg(feature = "chrono-tz")]
+    let timezone = parse_timezone(&args);
+    let local_time = |datetime: DateTime<Utc>| -> DateTime<FixedOffset> {
+        #[cfg(feature = "chrono-tz")]
+        if let Some(timezone) = timezone {
+            let local = datetime.with_timezone(&timezone);
+            return local.with_timezone(&local.offset().fix());
+        }
+        datetime.with_timezone(&FixedOffset::east(0))
+    };
+
+    if args.next {
+        let now = Utc::now();
+        let prayers = PrayerTimes::new(now.date(), coordinates, params);
+        match next_prayer(&prayers, now) {
+            Some((prayer, time, remaining)) => {
+                let local = local_time(time);
+                println!(
+                    "{} {} (in {})",
+                    prayer.name_for(local.weekday()),
+                    args.time_format.format(local),
+                    format_remaining(remaining)
+                );
+            }
+            None => {
+                eprintln!("The next prayer time cannot be computed for this location");
+                std::process::exit(1);
+            }
+        }
+        return;
     }
 
     let prayers = PrayerTimes::new(date, coordinates, params);
+    let schedule = [
+        (Prayer::Imsak, "Imsak"),
+        (Prayer::Fajr, "Fajr"),
+        (Prayer::Sunrise, "Sunrise"),
+        (Prayer::Duha, "Duha"),
+        (Prayer::Dhuhr, "Dhuhr"),
+        (Prayer::Asr, "Asr"),
+        (Prayer::Maghrib, "Maghrib"),
+        (Prayer::Isha, "Isha"),
+        (Prayer::MiddleOfTheNight, "Midnight"),
+        (Prayer::Qiyam, "Qiyam"),
+        (Prayer::FajrTomorrow, "Fajr"),
+    ]
+    .into_iter()
+    .filter(|(prayer, _)| match args.prayer {
+        Some(_) => is_selected(*prayer, args.prayer),
+        // The full table covers the current day only
+        None => *prayer != Prayer::FajrTomorrow,
+    })
+    .map(|(prayer, name)| (name, prayers.prayer_time(prayer)));
+
+    match args.format {
+        Format::Text => {
+            let format_dt = |pt: &PrayerTime| -> String {
+                pt.as_datetime()
+                    .map(|dt| args.time_format.format(local_time(*dt)))
+                    .unwrap_or_default()
+            };
+
+            if args.prayer.is_some() {
+                for (_, prayer_time) in schedule {
+                    println!("{}", format_dt(&prayer_time));
+                }
+                return;
+            }
+
+            println!(
+                "Using coordinates: {},{}, method: {:?}\n",
+                coordinates.latitude, coordinates.longitude, method
+            );
+            for (name, prayer_time) in schedule {
+                println!("{:<9}: {}", name, format_dt(&prayer_time));
+            }
+        }
+        Format::Json => print_json(&prayers, args.prayer),
+        Format::Csv => {
+            println!("prayer,time");
+            for (name, prayer_time) in schedule {
+                let time = prayer_time
+                    .datetime
+                    .map(|dt| local_time(dt).to_rfc3339())
+                    .unwrap_or_default();
+                println!("{},{}", name, time);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_prayers_to_print() {
+        assert!(is_selected(Prayer::Fajr, None));
+        assert!(is_selected(Prayer::Fajr, Some(Prayer::Fajr)));
+        assert!(!is_selected(Prayer::Isha, Some(Prayer::Fajr)));
+    }
+
+    #[test]
+    fn format_time_with_12_and_24_hour_clocks() {
+        let datetime = Utc.ymd(2022, 8, 1).and_hms(13, 5, 0);
+
+        assert_eq!(TimeFormat::TwentyFourHour.format(datetime), "13:05");
+        assert_eq!(TimeFormat::TwelveHour.format(datetime), "01:05 PM");
+
+        let datetime = Utc.ymd(2022, 8, 1).and_hms(13, 5, 40);
+        assert_eq!(TimeFormat::TwentyFourHour.format(datetime), "13:06");
+    }
+
+    #[test]
+    fn format_method_angles_and_intervals() {
+        assert_eq!(
+            format_method(
+                "muslim-world-league",
+                &Method::MuslimWorldLeague.parameters()
+            ),
+            "muslim-world-league      fajr: 18°, isha: 17°"
+        );
+
+        let params = ParametersBuilder::from_method(Method::Other, Madhab::Shafi)
+            .fajr_interval(90)
+            .isha_interval(90)
+            .build();
+        assert_eq!(
+            format_method("other", &params),
+            "other                    fajr: 90 minutes before sunrise, isha: 90 minutes after maghrib"
+        );
+    }
+
+    #[test]
+    fn format_qibla_direction_and_distance() {
+        let qiblah = Qiblah::new(Coordinates::new(36.8065, 10.1815));
+
+        assert_eq!(
+            format_qibla(&qiblah),
+            "Qibla: 112.7° (ESE), distance to the Kaaba: 3328 km"
+        );
+    }
+
+    #[test]
+    fn select_next_prayer() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+
+        let now = Utc.ymd(2022, 8, 1).and_hms(10, 0, 0);
+        let (prayer, time, remaining) = next_prayer(&prayers, now).unwrap();
+        assert_eq!(prayer, Prayer::Dhuhr);
+        assert_eq!(time, prayers.time(Prayer::Dhuhr));
+        assert_eq!(remaining, prayers.duration_until_next(now));
+
+        // Before Fajr
+        let now = Utc.ymd(2022, 8, 1).and_hms(1, 0, 0);
+        assert_eq!(next_prayer(&prayers, now).unwrap().0, Prayer::Fajr);
+
+        // After the last third of the night, the next day's Fajr
+        let now = prayers.time(Prayer::Qiyam) + Duration::minutes(1);
+        let (prayer, time, _) = next_prayer(&prayers, now).unwrap();
+        assert_eq!(prayer, Prayer::Fajr);
+        assert_eq!(time, prayers.time(Prayer::FajrTomorrow));
+    }
+
+    #[test]
+    fn parameters_follow_the_method_argument() {
+        let args = |method: &str, madhab: &str| {
+            Cli::parse_from([
+                "salati",
+                "-c",
+                "36.8065,10.1815",
+                "--method",
+                method,
+                "--madhab",
+                madhab,
+            ])
+        };
+        let date = Utc.ymd(2022, 8, 1);
 
-    let format_dt: fn(PrayerTime) -> String =
-        |pt| -> String { pt.datetime.unwrap().format("%H:%M %p").to_string() };
-    println!("Fajr     : {}", format_dt(prayers.fajr));
-    println!("Sunrise  : {}", format_dt(prayers.sunrise));
-    println!("Dhuhr    : {}", format_dt(prayers.dhuhr));
-    println!("Asr      : {}", format_dt(prayers.asr));
-    println!("Maghrib  : {}", format_dt(prayers.maghrib));
-    println!("Isha     : {}", format_dt(prayers.isha));
-    println!("Midnight : {}", format_dt(prayers.middle_of_the_night));
-    println!("Qiyam    : {}", format_dt(prayers.qiyam));
+        let (coordinates, mwl) = parameters_from_args(&args("muslim-world-league", "shafi"));
+        let (_, turkey) = parameters_from_args(&args("turkey", "hanafi"));
+        assert_eq!(turkey.method, Method::Turkey);
+        assert_eq!(turkey.madhab, Madhab::Hanafi);
+
+        let mwl_prayers = PrayerTimes::new(date, coordinates, mwl);
+        let turkey_prayers = PrayerTimes::new(date, coordinates, turkey);
+        assert_ne!(turkey_prayers.fajr, mwl_prayers.fajr);
+        assert_ne!(turkey_prayers.asr, mwl_prayers.asr);
+    }
+
+    #[test]
+    fn next_prayer_is_printed_as_text_only() {
+        let args = |extra: &[&str]| {
+            let mut args = vec!["salati", "-c", "36.8065,10.1815", "--method", "karachi"];
+            args.extend_from_slice(extra);
+            Cli::try_parse_from(args)
+        };
+
+        assert!(args(&["--next"]).is_ok());
+        assert!(args(&["--next", "--time-format", "12"]).is_ok());
+        assert!(args(&["--next", "--format", "json"]).is_err());
+        assert!(args(&["--next", "--format", "csv"]).is_err());
+    }
+
+    #[test]
+    fn config_conflicts_with_parameters_arguments() {
+        let args = |extra: &[&str]| {
+            let mut args = vec!["salati", "--config", "salati.toml"];
+            args.extend_from_slice(extra);
+            Cli::try_parse_from(args)
+        };
+
+        assert!(args(&[]).is_ok());
+        assert!(args(&["--qibla"]).is_ok());
+        assert!(args(&["--madhab", "hanafi"]).is_err());
+        assert!(args(&["--twilight", "white"]).is_err());
+        assert!(args(&["--high-latitude-rule", "seventh-of-the-night"]).is_err());
+        assert!(args(&["--polar-circle-resolution", "nearest-day"]).is_err());
+    }
+
+    #[test]
+    fn format_remaining_time() {
+        assert_eq!(format_remaining(Duration::minutes(65)), "1h 05m");
+        assert_eq!(format_remaining(Duration::seconds(89)), "0h 01m");
+    }
+
+    #[test]
+    fn parse_date_argument() {
+        assert_eq!(parse_date("2022-08-01"), Ok(Utc.ymd(2022, 8, 1)));
+        assert_eq!(
+            parse_date("2022-02-30"),
+            Err(String::from(
+                "invalid date `2022-02-30`, expected YYYY-MM-DD"
+            ))
+        );
+        assert!(parse_date("01/08/2022").is_err());
+    }
 }
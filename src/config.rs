@@ -0,0 +1,185 @@
+// Salati - Configuration
+//
+//! Configuration of a location and its calculation parameters,
+//! that can be loaded from a TOML or JSON file.
+use std::fmt;
+
+use crate::astronomy::unit::{CoordinateError, Coordinates};
+use crate::models::adjustments::TimeAdjustment;
+use crate::models::high_latitude_rule::HighLatitudeRule;
+use crate::models::madhab::Madhab;
+use crate::models::method::Method;
+use crate::models::parameters::{Parameters, ParametersBuilder};
+use crate::models::polar_circle_resolution::PolarCircleResolution;
+use crate::models::twilight::Twilight;
+
+/// A location and its calculation parameters,
+/// settings that are not provided default to the method's ones.
+#[derive(PartialEq, Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Config {
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default)]
+    pub elevation_meters: f64,
+    pub method: Method,
+    #[serde(default)]
+    pub madhab: Madhab,
+    pub twilight: Option<Twilight>,
+    /// If not provided, the recommended rule for the coordinates is used
+    pub high_latitude_rule: Option<HighLatitudeRule>,
+    pub polar_circle_resolution: Option<PolarCircleResolution>,
+    pub fajr_angle: Option<f64>,
+    /// Can't be used along with `isha_interval`
+    pub isha_angle: Option<f64>,
+    /// Can't be used along with `isha_angle`
+    pub isha_interval: Option<i32>,
+    #[serde(default)]
+    pub adjustments: TimeAdjustment,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ConfigError {
+    /// The coordinates are out of range
+    InvalidCoordinates(CoordinateError),
+    /// Both `isha_angle` and `isha_interval` are set
+    ConflictingIsha,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::InvalidCoordinates(error) => write!(f, "{}", error),
+            ConfigError::ConflictingIsha => {
+                write!(f, "`isha_angle` and `isha_interval` can't be used together")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<CoordinateError> for ConfigError {
+    fn from(error: CoordinateError) -> Self {
+        ConfigError::InvalidCoordinates(error)
+    }
+}
+
+impl Config {
+    /// Returns the coordinates and the parameters described by this configuration
+    pub fn into_parameters(&self) -> Result<(Coordinates, Parameters), ConfigError> {
+        if self.isha_angle.is_some() && self.isha_interval.is_some() {
+            return Err(ConfigError::ConflictingIsha);
+        }
+
+        Coordinates::try_new(self.latitude, self.longitude)?;
+        let coordinates =
+            Coordinates::with_elevation(self.latitude, self.longitude, self.elevation_meters);
+
+        // Method::Other relies on the configured angles
+        let mut builder = ParametersBuilder::from_method(self.method, self.madhab);
+        if let Some(fajr_angle) = self.fajr_angle {
+            builder.fajr_angle(fajr_angle);
+        }
+        if let Some(isha_angle) = self.isha_angle {
+            builder.isha_angle(isha_angle);
+        }
+        if let Some(isha_interval) = self.isha_interval {
+            builder.isha_interval(isha_interval);
+        }
+        if let Some(twilight) = self.twilight {
+            builder.twilight(twilight);
+        }
+        if let Some(polar_circle_resolution) = self.polar_circle_resolution {
+            builder.polar_circle_resolution(polar_circle_resolution);
+        }
+        let high_latitude_rule = self
+            .high_latitude_rule
+            .unwrap_or_else(|| HighLatitudeRule::recommended(coordinates));
+
+        let parameters = builder
+            .high_latitude_rule(high_latitude_rule)
+            .adjustments(self.adjustments)
+            .build();
+
+        Ok((coordinates, parameters))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_json_config() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "latitude": 36.8065,
+                "longitude": 10.1815,
+                "method": "umm-al-qura",
+                "madhab": "hanafi",
+                "high_latitude_rule": "seventh-of-the-night",
+                "isha_interval": 120,
+                "adjustments": {"dhuhr": 2}
+            }"#,
+        )
+        .unwrap();
+        let (coordinates, parameters) = config.into_parameters().unwrap();
+
+        assert_eq!(coordinates, Coordinates::new(36.8065, 10.1815));
+        assert_eq!(parameters.method, Method::UmmAlQura);
+        assert_eq!(parameters.madhab, Madhab::Hanafi);
+        assert_eq!(parameters.fajr_angle, 18.5);
+        assert_eq!(parameters.isha_interval, 120);
+        assert_eq!(
+            parameters.high_latitude_rule,
+            HighLatitudeRule::SeventhOfTheNight
+        );
+        assert_eq!(parameters.adjustments.dhuhr, 2);
+    }
+
+    #[test]
+    fn deserialize_toml_config() {
+        let config: Config = toml::from_str(
+            r#"
+            latitude = 51.5072
+            longitude = -0.1276
+            method = "moonsighting-committee"
+            twilight = "white"
+            fajr_angle = 15.0
+            "#,
+        )
+        .unwrap();
+        let (coordinates, parameters) = config.into_parameters().unwrap();
+
+        assert_eq!(coordinates, Coordinates::new(51.5072, -0.1276));
+        assert_eq!(parameters.method, Method::MoonsightingCommittee);
+        assert_eq!(parameters.madhab, Madhab::Shafi);
+        assert_eq!(parameters.twilight, Twilight::White);
+        assert_eq!(parameters.fajr_angle, 15.0);
+        assert_eq!(
+            parameters.high_latitude_rule,
+            HighLatitudeRule::recommended(coordinates)
+        );
+    }
+
+    #[test]
+    fn reject_invalid_config() {
+        let mut config: Config = serde_json::from_str(
+            r#"{"latitude": 36.8065, "longitude": 10.1815, "method": "karachi"}"#,
+        )
+        .unwrap();
+
+        config.isha_angle = Some(17.0);
+        config.isha_interval = Some(90);
+        assert_eq!(config.into_parameters(), Err(ConfigError::ConflictingIsha));
+
+        config.isha_interval = None;
+        config.latitude = 510.0;
+        assert_eq!(
+            config.into_parameters(),
+            Err(ConfigError::InvalidCoordinates(
+                CoordinateError::LatitudeOutOfRange(510.0)
+            ))
+        );
+    }
+}
@@ -3,13 +3,52 @@ use crate::models::method::Method;
 
 pub static HIGH_LATITUDE_THRESHOLD: f64 = 48.0;
 pub static MOONSIGHTING_COMITTEE_HIGH_LATITUDE: f64 = 55.0;
+pub static EARTH_RADIUS_KM: f64 = 6371.0;
+pub static KILOMETERS_PER_MILE: f64 = 1.609344;
+/// Standard atmospheric pressure (in hPa) used to calculate the refraction at sunrise/sunset
+pub static STANDARD_PRESSURE_HPA: f64 = 1010.0;
+/// Standard temperature (in °C) used to calculate the refraction at sunrise/sunset
+pub static STANDARD_TEMPERATURE_C: f64 = 10.0;
+/// Coordinates of the Kaaba in Makkah, the qiblah
+pub static KAABA_COORDINATES: Coordinates = Coordinates {
+    latitude: 21.4225241,
+    longitude: 39.8261818,
+    elevation_meters: 0.0,
+};
 pub static HIGH_LATITUDE_RESOLUTION_MESSAGE: &str = "At higher latitudes, where Fajr and Isha times are very close to each other, we fallback to high latitude resolution strategy.";
+pub static POLAR_CIRCLE_RESOLUTION_MESSAGE: &str = "In polar circle regions, where the sun does not set or rise, we fallback to polar circle resolution strategy.";
+pub static UNRESOLVED_POLAR_CIRCLE_MESSAGE: &str =
+    "The sun does not set or rise at this date and location, prayer times cannot be resolved.";
+pub static FAJR_AFTER_SUNRISE_MESSAGE: &str = "Fajr time cannot be after sunrise.";
+pub static ISHA_BEFORE_MAGHRIB_MESSAGE: &str = "Isha time cannot be before maghrib.";
+pub static UNORDERED_PRAYER_TIME_MESSAGE: &str =
+    "Prayer time cannot be before the previous prayer of the day.";
+/// Maximum number of days to look for, backward and forward, to find a day with sunrise and sunset
+pub static NEAREST_DAY_SEARCH_LIMIT: i64 = 182;
+/// Latitude of the nearest habitable town, used to resolve prayer times in polar circle regions
+pub static NEAREST_TOWN_LATITUDE: f64 = 48.0;
+/// Minutes after sunset for Isha, used by the Umm al-Qura polar circle resolution
+pub static UMM_AL_QURA_ISHA_INTERVAL: i64 = 90;
+/// Minutes after sunset for Isha during Ramadan, used by Umm al-Qura and Qatar
+#[cfg(feature = "hijri")]
+pub static RAMADAN_ISHA_INTERVAL: i32 = 120;
 
+/// Scale of the coordinates quantization of cache keys, i.e. 4 decimal places (~11 meters)
+pub static CACHE_KEY_COORDINATES_SCALE: f64 = 10_000.0;
+
+/// Twilight angle for which the scaled seventh of the night rule is exactly one seventh
+pub static SCALED_SEVENTH_REFERENCE_ANGLE: f64 = 18.0;
+
+/// Angle of the sun below the horizon at the end of the nautical twilight
+pub static NAUTICAL_TWILIGHT_ANGLE: f64 = 12.0;
+
+/// Returns true if the coordinates are at a high latitude, north or south,
+/// using the default threshold (see `Parameters::high_latitude_threshold` to customize it)
 pub fn is_high_latitude(coordinates: Coordinates, method: Option<Method>) -> bool {
     match method {
         Some(Method::MoonsightingCommittee) => {
-            coordinates.latitude >= MOONSIGHTING_COMITTEE_HIGH_LATITUDE
+            coordinates.latitude.abs() >= MOONSIGHTING_COMITTEE_HIGH_LATITUDE
         }
-        _ => coordinates.latitude >= HIGH_LATITUDE_THRESHOLD,
+        _ => coordinates.latitude.abs() >= HIGH_LATITUDE_THRESHOLD,
     }
 }
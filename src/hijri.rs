@@ -0,0 +1,141 @@
+// Salati - Hijri calendar
+//
+//! Conversion of Gregorian dates to the Hijri calendar using the tabular
+//! (arithmetical) Islamic calendar.
+use chrono::{Date, Datelike, Utc};
+
+/// Julian day number of 1 Muharram 1 AH (civil epoch) minus one day
+static HIJRI_EPOCH: i64 = 1948440;
+/// Julian day number of the day preceding 0001-01-01 (proleptic Gregorian calendar)
+static GREGORIAN_EPOCH: i64 = 1721425;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum HijriMonth {
+    Muharram,
+    Safar,
+    RabiAlAwwal,
+    RabiAlThani,
+    JumadaAlUla,
+    JumadaAlAkhirah,
+    Rajab,
+    Shaban,
+    Ramadan,
+    Shawwal,
+    DhuAlQadah,
+    DhuAlHijjah,
+}
+
+impl HijriMonth {
+    /// Returns the month of the given number (1-12)
+    pub fn from_number(month: u32) -> Option<HijriMonth> {
+        match month {
+            1 => Some(HijriMonth::Muharram),
+            2 => Some(HijriMonth::Safar),
+            3 => Some(HijriMonth::RabiAlAwwal),
+            4 => Some(HijriMonth::RabiAlThani),
+            5 => Some(HijriMonth::JumadaAlUla),
+            6 => Some(HijriMonth::JumadaAlAkhirah),
+            7 => Some(HijriMonth::Rajab),
+            8 => Some(HijriMonth::Shaban),
+            9 => Some(HijriMonth::Ramadan),
+            10 => Some(HijriMonth::Shawwal),
+            11 => Some(HijriMonth::DhuAlQadah),
+            12 => Some(HijriMonth::DhuAlHijjah),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            HijriMonth::Muharram => String::from("Muharram"),
+            HijriMonth::Safar => String::from("Safar"),
+            HijriMonth::RabiAlAwwal => String::from("Rabi al-Awwal"),
+            HijriMonth::RabiAlThani => String::from("Rabi al-Thani"),
+            HijriMonth::JumadaAlUla => String::from("Jumada al-Ula"),
+            HijriMonth::JumadaAlAkhirah => String::from("Jumada al-Akhirah"),
+            HijriMonth::Rajab => String::from("Rajab"),
+            HijriMonth::Shaban => String::from("Sha'ban"),
+            HijriMonth::Ramadan => String::from("Ramadan"),
+            HijriMonth::Shawwal => String::from("Shawwal"),
+            HijriMonth::DhuAlQadah => String::from("Dhu al-Qa'dah"),
+            HijriMonth::DhuAlHijjah => String::from("Dhu al-Hijjah"),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct HijriDate {
+    pub year: i32,
+    /// Month of the year, from 1 (Muharram) to 12 (Dhu al-Hijjah)
+    pub month: u32,
+    pub day: u32,
+}
+
+impl HijriDate {
+    pub fn new(year: i32, month: u32, day: u32) -> HijriDate {
+        HijriDate { year, month, day }
+    }
+
+    /// Converts the given gregorian date using the tabular Islamic calendar
+    ///
+    /// Note that the tabular calendar may differ by a day from the
+    /// moon sighting based calendars.
+    pub fn from_gregorian(date: Date<Utc>) -> HijriDate {
+        let julian_day = date.num_days_from_ce() as i64 + GREGORIAN_EPOCH;
+
+        let mut l = julian_day - HIJRI_EPOCH + 10632;
+        let n = (l - 1) / 10631;
+        l = l - 10631 * n + 354;
+        let j = ((10985 - l) / 5316) * ((50 * l) / 17719) + (l / 5670) * ((43 * l) / 15238);
+        l = l - ((30 - j) / 15) * ((17719 * j) / 50) - (j / 16) * ((15238 * j) / 43) + 29;
+        let month = (24 * l) / 709;
+        let day = l - (709 * month) / 24;
+        let year = 30 * n + j - 30;
+
+        HijriDate::new(year as i32, month as u32, day as u32)
+    }
+
+    pub fn hijri_month(&self) -> HijriMonth {
+        HijriMonth::from_number(self.month).expect("Invalid hijri month")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn convert_gregorian_to_hijri() {
+        assert_eq!(
+            HijriDate::from_gregorian(Utc.ymd(2000, 1, 1)),
+            HijriDate::new(1420, 9, 24)
+        );
+        assert_eq!(
+            HijriDate::from_gregorian(Utc.ymd(2022, 7, 30)),
+            HijriDate::new(1444, 1, 1)
+        );
+        assert_eq!(
+            HijriDate::from_gregorian(Utc.ymd(2022, 8, 1)),
+            HijriDate::new(1444, 1, 3)
+        );
+        assert_eq!(
+            HijriDate::from_gregorian(Utc.ymd(2023, 3, 23)),
+            HijriDate::new(1444, 9, 1)
+        );
+        assert_eq!(
+            HijriDate::from_gregorian(Utc.ymd(2024, 3, 11)),
+            HijriDate::new(1445, 9, 1)
+        );
+    }
+
+    #[test]
+    fn hijri_month_name() {
+        let date = HijriDate::from_gregorian(Utc.ymd(2023, 3, 23));
+
+        assert_eq!(date.hijri_month(), HijriMonth::Ramadan);
+        assert_eq!(date.hijri_month().name(), "Ramadan");
+        assert_eq!(HijriMonth::DhuAlHijjah.name(), "Dhu al-Hijjah");
+        assert_eq!(HijriMonth::from_number(13), None);
+    }
+}
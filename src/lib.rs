@@ -19,35 +19,82 @@
 //! println!("isha: {}", prayers.isha.datetime.unwrap());
 //! ```
 mod astronomy;
+#[cfg(feature = "serde")]
+mod config;
 mod constants;
+#[cfg(feature = "hijri")]
+mod hijri;
 mod models;
+mod moonsighting;
+mod prayer_schedule;
 mod prayer_times;
+mod prayer_times_local;
 
 pub mod prelude {
     #[doc(no_inline)]
     pub use crate::astronomy::qiblah::Qiblah;
     #[doc(no_inline)]
-    pub use crate::astronomy::unit::{Coordinates, Stride};
+    pub use crate::astronomy::solar::SolarTime;
+    #[doc(no_inline)]
+    pub use crate::astronomy::unit::{
+        wrap_degrees, Angle, CoordinateError, Coordinates, Normalize, ParseCoordinatesError, Stride,
+    };
+    #[cfg(feature = "serde")]
+    #[doc(no_inline)]
+    pub use crate::config::{Config, ConfigError};
+    #[doc(no_inline)]
+    pub use crate::constants::KAABA_COORDINATES;
+    #[cfg(feature = "hijri")]
+    #[doc(no_inline)]
+    pub use crate::hijri::{HijriDate, HijriMonth};
     #[doc(no_inline)]
     pub use crate::models::adjustments::{TimeAdjustment, TimeAdjustmentBuilder};
     #[doc(no_inline)]
+    pub use crate::models::cache_key::CacheKey;
+    #[doc(no_inline)]
+    pub use crate::models::custom_method::{CustomMethod, CustomMethodError};
+    #[doc(no_inline)]
+    pub use crate::models::day_type::DayType;
+    #[doc(no_inline)]
+    pub use crate::models::fajr_floor::FajrFloor;
+    #[doc(no_inline)]
+    pub use crate::models::forbidden_durations::ForbiddenDurations;
+    #[doc(no_inline)]
     pub use crate::models::high_latitude_rule::HighLatitudeRule;
     #[doc(no_inline)]
+    pub use crate::models::iqamah::{IqamahOffsets, IqamahOffsetsBuilder};
+    #[doc(no_inline)]
+    pub use crate::models::language::Language;
+    #[doc(no_inline)]
     pub use crate::models::madhab::Madhab;
     #[doc(no_inline)]
-    pub use crate::models::method::Method;
+    pub use crate::models::method::{Method, ParseMethodError};
     #[doc(no_inline)]
-    pub use crate::models::parameters::{Parameters, ParametersBuilder};
+    pub use crate::models::parameters::{Parameters, ParametersBuilder, ParametersError};
     #[doc(no_inline)]
     pub use crate::models::polar_circle_resolution::PolarCircleResolution;
     #[doc(no_inline)]
     pub use crate::models::prayer::Prayer;
     #[doc(no_inline)]
-    pub use crate::models::prayer_time::PrayerTime;
+    pub use crate::models::prayer_category::PrayerCategory;
+    #[doc(no_inline)]
+    pub use crate::models::prayer_time::{PrayerTime, PrayerTimeResolution};
+    #[doc(no_inline)]
+    pub use crate::models::rounding::Rounding;
     #[doc(no_inline)]
     pub use crate::models::twilight::Twilight;
     #[doc(no_inline)]
+    pub use crate::moonsighting::{
+        season_adjusted_evening_twilight, season_adjusted_morning_twilight,
+    };
+    #[doc(no_inline)]
+    pub use crate::prayer_schedule::PrayerSchedule;
+    #[doc(no_inline)]
     pub use crate::prayer_times::PrayerTimes;
     #[doc(no_inline)]
-    pub use chrono::{Date, DateTime, Datelike, Duration, Local, TimeZone, Timelike, Utc};
+    pub use crate::prayer_times_local::PrayerTimesLocal;
+    #[doc(no_inline)]
+    pub use chrono::{
+        Date, DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike, Utc,
+    };
 }
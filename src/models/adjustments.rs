@@ -3,7 +3,9 @@ use std::default::Default;
 /// Time adjustment for all prayer times.
 /// The value is specified in *minutes* and
 /// can be either positive or negative.
-#[derive(PartialEq, Debug, Default, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TimeAdjustment {
     pub fajr: i64,
     pub sunrise: i64,
@@ -11,6 +13,8 @@ pub struct TimeAdjustment {
     pub asr: i64,
     pub maghrib: i64,
     pub isha: i64,
+    pub middle_of_the_night: i64,
+    pub qiyam: i64,
 }
 
 impl TimeAdjustment {
@@ -22,6 +26,8 @@ impl TimeAdjustment {
             asr,
             maghrib,
             isha,
+            middle_of_the_night: 0,
+            qiyam: 0,
         }
     }
 }
@@ -36,6 +42,8 @@ pub struct TimeAdjustmentBuilder {
     asr: i64,
     maghrib: i64,
     isha: i64,
+    middle_of_the_night: i64,
+    qiyam: i64,
 }
 
 impl TimeAdjustmentBuilder {
@@ -47,6 +55,8 @@ impl TimeAdjustmentBuilder {
             asr: 0,
             maghrib: 0,
             isha: 0,
+            middle_of_the_night: 0,
+            qiyam: 0,
         }
     }
 
@@ -80,6 +90,16 @@ impl TimeAdjustmentBuilder {
         self
     }
 
+    pub fn middle_of_the_night(&mut self, middle_of_the_night: i64) -> &mut TimeAdjustmentBuilder {
+        self.middle_of_the_night = middle_of_the_night;
+        self
+    }
+
+    pub fn qiyam(&mut self, qiyam: i64) -> &mut TimeAdjustmentBuilder {
+        self.qiyam = qiyam;
+        self
+    }
+
     pub fn build(&self) -> TimeAdjustment {
         TimeAdjustment {
             fajr: self.fajr,
@@ -88,6 +108,8 @@ impl TimeAdjustmentBuilder {
             asr: self.asr,
             maghrib: self.maghrib,
             isha: self.isha,
+            middle_of_the_night: self.middle_of_the_night,
+            qiyam: self.qiyam,
         }
     }
 }
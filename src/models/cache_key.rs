@@ -0,0 +1,84 @@
+use super::parameters::Parameters;
+use crate::astronomy::unit::Coordinates;
+use crate::constants::CACHE_KEY_COORDINATES_SCALE;
+
+/// A hashable key identifying a location and its parameters, useful to memoize
+/// prayer times computations (e.g. in a `HashMap`).
+///
+/// Coordinates are quantized to 4 decimal places (~11 meters), so that close locations
+/// share the same key.
+#[derive(PartialEq, Hash, Debug, Copy, Clone)]
+pub struct CacheKey {
+    latitude: i64,
+    longitude: i64,
+    elevation_meters: i64,
+    parameters: Parameters,
+}
+
+// Parameters are only compared exactly, and are never NaN in practice
+impl Eq for CacheKey {}
+
+impl CacheKey {
+    pub fn new(coordinates: Coordinates, parameters: Parameters) -> CacheKey {
+        let quantize = |value: f64| (value * CACHE_KEY_COORDINATES_SCALE).round() as i64;
+
+        CacheKey {
+            latitude: quantize(coordinates.latitude),
+            longitude: quantize(coordinates.longitude),
+            elevation_meters: coordinates.elevation_meters.round() as i64,
+            parameters,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::madhab::Madhab;
+    use crate::models::method::Method;
+    use crate::models::parameters::ParametersBuilder;
+    use std::collections::HashMap;
+
+    #[test]
+    fn close_coordinates_share_the_same_key() {
+        let params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+
+        assert_eq!(
+            CacheKey::new(Coordinates::new(36.80651, 10.18152), params),
+            CacheKey::new(Coordinates::new(36.80649, 10.18148), params)
+        );
+        assert_ne!(
+            CacheKey::new(Coordinates::new(36.8065, 10.1815), params),
+            CacheKey::new(Coordinates::new(36.8066, 10.1815), params)
+        );
+    }
+
+    #[test]
+    fn memoize_by_cache_key() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let mut cache = HashMap::new();
+        cache.insert(
+            CacheKey::new(
+                tunis,
+                ParametersBuilder::with(Method::Karachi, Madhab::Shafi),
+            ),
+            "karachi",
+        );
+        cache.insert(
+            CacheKey::new(
+                tunis,
+                ParametersBuilder::with(Method::Karachi, Madhab::Hanafi),
+            ),
+            "karachi-hanafi",
+        );
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.get(&CacheKey::new(
+                Coordinates::new(36.80652, 10.18149),
+                ParametersBuilder::with(Method::Karachi, Madhab::Hanafi)
+            )),
+            Some(&"karachi-hanafi")
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use super::adjustments::TimeAdjustment;
+use super::high_latitude_rule::HighLatitudeRule;
+use super::twilight::Twilight;
+
+/// Calculation method of a local authority (e.g. a mosque) that is not built in,
+/// it can be loaded at runtime from a configuration file.
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustomMethod {
+    pub fajr_angle: f64,
+    /// Can't be used along with `isha_interval`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isha_angle: Option<f64>,
+    /// Minutes after Maghrib, can't be used along with `isha_angle`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isha_interval: Option<i32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adjustments: TimeAdjustment,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub high_latitude_rule: HighLatitudeRule,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub twilight: Twilight,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum CustomMethodError {
+    /// The angle is not in the range (0, 90)
+    InvalidAngle(f64),
+    /// The Isha interval is not positive
+    InvalidIshaInterval(i32),
+    /// Neither `isha_angle` nor `isha_interval` is set
+    MissingIsha,
+    /// Both `isha_angle` and `isha_interval` are set
+    ConflictingIsha,
+}
+
+impl fmt::Display for CustomMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CustomMethodError::InvalidAngle(angle) => {
+                write!(f, "angle {} is out of range (0, 90)", angle)
+            }
+            CustomMethodError::InvalidIshaInterval(interval) => {
+                write!(f, "isha interval {} must be positive", interval)
+            }
+            CustomMethodError::MissingIsha => {
+                write!(f, "either `isha_angle` or `isha_interval` must be set")
+            }
+            CustomMethodError::ConflictingIsha => {
+                write!(f, "`isha_angle` and `isha_interval` can't be used together")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CustomMethodError {}
+
+impl CustomMethod {
+    /// Checks that the angles are in range and that Isha is defined exactly once
+    pub fn validate(&self) -> Result<(), CustomMethodError> {
+        let check_angle = |angle: f64| {
+            if angle > 0.0 && angle < 90.0 {
+                Ok(())
+            } else {
+                Err(CustomMethodError::InvalidAngle(angle))
+            }
+        };
+
+        check_angle(self.fajr_angle)?;
+        match (self.isha_angle, self.isha_interval) {
+            (Some(_), Some(_)) => Err(CustomMethodError::ConflictingIsha),
+            (None, None) => Err(CustomMethodError::MissingIsha),
+            (Some(isha_angle), None) => check_angle(isha_angle),
+            (None, Some(isha_interval)) if isha_interval <= 0 => {
+                Err(CustomMethodError::InvalidIshaInterval(isha_interval))
+            }
+            (None, Some(_)) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astronomy::unit::Coordinates;
+    use crate::models::madhab::Madhab;
+    use crate::models::method::Method;
+    use crate::models::parameters::Parameters;
+    use crate::prayer_schedule::PrayerSchedule;
+    use chrono::{TimeZone, Utc};
+
+    fn local_mosque() -> CustomMethod {
+        CustomMethod {
+            fajr_angle: 17.5,
+            isha_angle: None,
+            isha_interval: Some(80),
+            adjustments: TimeAdjustment::new(0, 0, 2, 0, 3, 0),
+            high_latitude_rule: HighLatitudeRule::SeventhOfTheNight,
+            twilight: Twilight::Red,
+        }
+    }
+
+    #[test]
+    fn parameters_from_custom_method() {
+        let params = Parameters::from_custom(&local_mosque(), Madhab::Hanafi).unwrap();
+
+        assert_eq!(params.method, Method::Other);
+        assert_eq!(params.madhab, Madhab::Hanafi);
+        assert_eq!(params.fajr_angle, 17.5);
+        assert_eq!(params.isha_interval, 80);
+        assert_eq!(params.method_adjustments.dhuhr, 2);
+        assert_eq!(params.adjustments, TimeAdjustment::default());
+        assert_eq!(
+            params.high_latitude_rule,
+            HighLatitudeRule::SeventhOfTheNight
+        );
+
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let schedule = PrayerSchedule::new(Utc.ymd(2022, 8, 1), 7, tunis, params);
+        for prayers in schedule.iter() {
+            assert!(prayers.fajr.is_valid());
+            assert_eq!(
+                prayers.isha.datetime.unwrap() - prayers.solar_sunset.datetime.unwrap(),
+                chrono::Duration::minutes(80)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_custom_method() {
+        let mut method = local_mosque();
+        assert_eq!(method.validate(), Ok(()));
+
+        method.isha_angle = Some(17.0);
+        assert_eq!(method.validate(), Err(CustomMethodError::ConflictingIsha));
+
+        method.isha_interval = None;
+        assert_eq!(method.validate(), Ok(()));
+
+        method.isha_angle = None;
+        assert_eq!(method.validate(), Err(CustomMethodError::MissingIsha));
+
+        method.isha_interval = Some(-10);
+        assert_eq!(
+            method.validate(),
+            Err(CustomMethodError::InvalidIshaInterval(-10))
+        );
+
+        method.isha_interval = Some(90);
+        method.fajr_angle = 95.0;
+        assert_eq!(
+            method.validate(),
+            Err(CustomMethodError::InvalidAngle(95.0))
+        );
+        assert_eq!(
+            Parameters::from_custom(&method, Madhab::Shafi),
+            Err(CustomMethodError::InvalidAngle(95.0))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_custom_method() {
+        let method: CustomMethod = serde_json::from_str(
+            r#"{
+                "fajr_angle": 17.5,
+                "isha_interval": 80,
+                "adjustments": {"dhuhr": 2, "maghrib": 3},
+                "high_latitude_rule": "seventh-of-the-night"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(method, local_mosque());
+    }
+}
@@ -0,0 +1,18 @@
+/// Kind of day at a given date and location
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum DayType {
+    /// The sun rises and sets
+    Normal,
+    /// The sun does not set
+    MidnightSun,
+    /// The sun does not rise
+    PolarNight,
+}
+
+impl Default for DayType {
+    fn default() -> Self {
+        DayType::Normal
+    }
+}
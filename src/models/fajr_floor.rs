@@ -0,0 +1,21 @@
+use clap::ValueEnum;
+
+/// When Fajr is bounded by the night portion of the high latitude rule,
+/// i.e. Fajr is the later of the angle based time and the portion based time
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum FajrFloor {
+    /// Fajr is only based on the angle, it's unknown when the sun doesn't reach it
+    None,
+    /// Fajr is bounded above the high latitude threshold
+    HighLatitudeOnly,
+    /// Fajr is bounded at all latitudes
+    Always,
+}
+
+impl Default for FajrFloor {
+    fn default() -> Self {
+        FajrFloor::HighLatitudeOnly
+    }
+}
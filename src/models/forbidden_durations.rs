@@ -0,0 +1,30 @@
+use std::default::Default;
+
+/// Minutes of the makruh (disliked) windows, during which voluntary prayers
+/// should not be performed.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForbiddenDurations {
+    /// Minutes after sunrise, until the sun has fully risen
+    pub sunrise: i64,
+    /// Minutes before the solar transit, while the sun is at its zenith
+    pub zenith: i64,
+    /// Minutes before sunset, while the sun is setting
+    pub sunset: i64,
+}
+
+impl ForbiddenDurations {
+    pub fn new(sunrise: i64, zenith: i64, sunset: i64) -> Self {
+        ForbiddenDurations {
+            sunrise,
+            zenith,
+            sunset,
+        }
+    }
+}
+
+impl Default for ForbiddenDurations {
+    fn default() -> Self {
+        ForbiddenDurations::new(15, 5, 15)
+    }
+}
@@ -3,11 +3,21 @@ use clap::ValueEnum;
 use crate::astronomy::unit::Coordinates;
 use crate::constants::is_high_latitude;
 
-#[derive(PartialEq, Debug, Copy, Clone, ValueEnum)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum HighLatitudeRule {
     MiddleOfTheNight,
     SeventhOfTheNight,
     TwilightAngle,
+    /// The night portion is the time needed by the sun to reach the Fajr/Isha angle
+    /// based on its angular velocity at the given latitude
+    AngleBased,
+    /// Similar to AngleBased, but using the nautical twilight angle (12 degrees)
+    NauticalTwilight,
+    /// One seventh of the night, scaled by the ratio of the Fajr/Isha angle
+    /// to the reference angle (18 degrees)
+    ScaledSeventhOfTheNight,
 }
 
 impl Default for HighLatitudeRule {
@@ -17,6 +27,9 @@ impl Default for HighLatitudeRule {
 }
 
 impl HighLatitudeRule {
+    /// Returns the seventh of the night rule above the default high latitude threshold
+    /// (48° north or south), the middle of the night rule otherwise.
+    /// A custom `Parameters::high_latitude_threshold` is not taken into account.
     pub fn recommended(coordinates: Coordinates) -> Self {
         match is_high_latitude(coordinates, None) {
             true => HighLatitudeRule::SeventhOfTheNight,
@@ -0,0 +1,81 @@
+use std::default::Default;
+
+/// Minutes between the adhan and the iqamah (congregation) of each prayer.
+#[derive(PartialEq, Eq, Hash, Debug, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IqamahOffsets {
+    pub fajr: i64,
+    pub dhuhr: i64,
+    pub asr: i64,
+    pub maghrib: i64,
+    pub isha: i64,
+}
+
+impl IqamahOffsets {
+    pub fn new(fajr: i64, dhuhr: i64, asr: i64, maghrib: i64, isha: i64) -> Self {
+        IqamahOffsets {
+            fajr,
+            dhuhr,
+            asr,
+            maghrib,
+            isha,
+        }
+    }
+}
+
+/// Builder struct for the [IqamahOffsets](struct.IqamahOffsets.html).
+#[derive(Default)]
+pub struct IqamahOffsetsBuilder {
+    fajr: i64,
+    dhuhr: i64,
+    asr: i64,
+    maghrib: i64,
+    isha: i64,
+}
+
+impl IqamahOffsetsBuilder {
+    pub fn new() -> Self {
+        IqamahOffsetsBuilder {
+            fajr: 0,
+            dhuhr: 0,
+            asr: 0,
+            maghrib: 0,
+            isha: 0,
+        }
+    }
+
+    pub fn fajr(&mut self, fajr: i64) -> &mut IqamahOffsetsBuilder {
+        self.fajr = fajr;
+        self
+    }
+
+    pub fn dhuhr(&mut self, dhuhr: i64) -> &mut IqamahOffsetsBuilder {
+        self.dhuhr = dhuhr;
+        self
+    }
+
+    pub fn asr(&mut self, asr: i64) -> &mut IqamahOffsetsBuilder {
+        self.asr = asr;
+        self
+    }
+
+    pub fn maghrib(&mut self, maghrib: i64) -> &mut IqamahOffsetsBuilder {
+        self.maghrib = maghrib;
+        self
+    }
+
+    pub fn isha(&mut self, isha: i64) -> &mut IqamahOffsetsBuilder {
+        self.isha = isha;
+        self
+    }
+
+    pub fn build(&self) -> IqamahOffsets {
+        IqamahOffsets {
+            fajr: self.fajr,
+            dhuhr: self.dhuhr,
+            asr: self.asr,
+            maghrib: self.maghrib,
+            isha: self.isha,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use clap::ValueEnum;
+
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+/// Languages supported to display prayer names
+pub enum Language {
+    English,
+    Arabic,
+    French,
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::English
+    }
+}
@@ -1,7 +1,9 @@
 use clap::ValueEnum;
 
 /// Setting for the Asr prayer time.
-#[derive(PartialEq, Debug, Copy, Clone, ValueEnum)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum Madhab {
     Shafi = 1,
     Hanafi = 2,
@@ -1,3 +1,6 @@
+use std::fmt;
+use std::str::FromStr;
+
 use clap::ValueEnum;
 
 use super::adjustments::TimeAdjustmentBuilder;
@@ -6,7 +9,9 @@ use super::parameters::{Parameters, ParametersBuilder};
 
 /// Provides preset configuration for a few authorities
 /// for calculating prayer times.
-#[derive(PartialEq, Debug, Copy, Clone, ValueEnum)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum Method {
     /// Muslim World League
     MuslimWorldLeague,
@@ -38,6 +43,12 @@ pub enum Method {
     /// Singapore
     Singapore,
 
+    /// Shia Ithna-Ashari, Leva Institute, Qum
+    Jafari,
+
+    /// Diyanet İşleri Başkanlığı, Turkey
+    Turkey,
+
     /// Other
     Other,
 }
@@ -100,14 +111,83 @@ impl Method {
                 .method_adjustments(TimeAdjustmentBuilder::new().dhuhr(1).build())
                 .build(),
 
+            Method::Jafari => ParametersBuilder::new(16.0, 14.0)
+                .method(*self)
+                .maghrib_angle(4.0)
+                .build(),
+
+            Method::Turkey => ParametersBuilder::new(18.0, 17.0)
+                .method(*self)
+                .method_adjustments(
+                    TimeAdjustmentBuilder::new()
+                        .fajr(-2)
+                        .sunrise(-7)
+                        .dhuhr(5)
+                        .asr(4)
+                        .maghrib(7)
+                        .isha(1)
+                        .build(),
+                )
+                .build(),
+
             Method::Other => ParametersBuilder::new(0.0, 0.0).method(*self).build(),
         }
     }
 }
 
+/// Error returned when parsing an unknown method name
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParseMethodError(pub String);
+
+impl fmt::Display for ParseMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<_> = Method::value_variants()
+            .iter()
+            .filter_map(|method| method.to_possible_value())
+            .map(|value| value.get_name())
+            .collect();
+
+        write!(
+            f,
+            "Unknown method '{}', expected one of: {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseMethodError {}
+
+impl FromStr for Method {
+    type Err = ParseMethodError;
+
+    /// Parses a method from its name (e.g. "umm-al-qura" or "Umm_Al_Qura")
+    /// or a common alias (e.g. "mwl", "isna", "makkah", "egypt")
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let name = value.trim().to_lowercase().replace(['_', ' '], "-");
+
+        match name.as_str() {
+            "mwl" => Ok(Method::MuslimWorldLeague),
+            "isna" => Ok(Method::NorthAmerica),
+            "makkah" => Ok(Method::UmmAlQura),
+            "egypt" => Ok(Method::Egyptian),
+            _ => <Method as ValueEnum>::from_str(&name, true)
+                .map_err(|_| ParseMethodError(String::from(value))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::prayer::Prayer;
+
+    #[test]
+    fn parameters_of_every_method_are_valid() {
+        for method in Method::value_variants() {
+            assert_eq!(method.parameters().validate(), Ok(()), "{:?}", method);
+        }
+    }
 
     #[test]
     fn parameters_for_muslim_world_league() {
@@ -219,6 +299,35 @@ mod tests {
         assert_eq!(params.isha_interval, 0);
     }
 
+    #[test]
+    fn parameters_for_jafari() {
+        let method = Method::Jafari;
+        let params = method.parameters();
+
+        assert_eq!(params.method, Method::Jafari);
+        assert_eq!(params.fajr_angle, 16.0);
+        assert_eq!(params.isha_angle, 14.0);
+        assert_eq!(params.isha_interval, 0);
+        assert_eq!(params.maghrib_angle, Some(4.0));
+    }
+
+    #[test]
+    fn parameters_for_turkey() {
+        let method = Method::Turkey;
+        let params = method.parameters();
+
+        assert_eq!(params.method, Method::Turkey);
+        assert_eq!(params.fajr_angle, 18.0);
+        assert_eq!(params.isha_angle, 17.0);
+        assert_eq!(params.isha_interval, 0);
+        assert_eq!(params.time_adjustments(Prayer::Fajr), -2);
+        assert_eq!(params.time_adjustments(Prayer::Sunrise), -7);
+        assert_eq!(params.time_adjustments(Prayer::Dhuhr), 5);
+        assert_eq!(params.time_adjustments(Prayer::Asr), 4);
+        assert_eq!(params.time_adjustments(Prayer::Maghrib), 7);
+        assert_eq!(params.time_adjustments(Prayer::Isha), 1);
+    }
+
     #[test]
     fn parameters_for_other() {
         let method = Method::Other;
@@ -229,4 +338,24 @@ mod tests {
         assert_eq!(params.isha_angle, 0.0);
         assert_eq!(params.isha_interval, 0);
     }
+
+    #[test]
+    fn parse_method_from_name_or_alias() {
+        assert_eq!("karachi".parse(), Ok(Method::Karachi));
+        assert_eq!("Umm_Al_Qura".parse(), Ok(Method::UmmAlQura));
+        assert_eq!("MWL".parse(), Ok(Method::MuslimWorldLeague));
+        assert_eq!("isna".parse(), Ok(Method::NorthAmerica));
+        assert_eq!("makkah".parse(), Ok(Method::UmmAlQura));
+        assert_eq!("egypt".parse(), Ok(Method::Egyptian));
+    }
+
+    #[test]
+    fn parse_unknown_method() {
+        let error = "london".parse::<Method>().unwrap_err();
+
+        assert_eq!(error, ParseMethodError(String::from("london")));
+        assert!(error.to_string().starts_with(
+            "Unknown method 'london', expected one of: muslim-world-league, egyptian"
+        ));
+    }
 }
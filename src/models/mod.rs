@@ -1,9 +1,18 @@
 pub mod adjustments;
+pub mod cache_key;
+pub mod custom_method;
+pub mod day_type;
+pub mod fajr_floor;
+pub mod forbidden_durations;
 pub mod high_latitude_rule;
+pub mod iqamah;
+pub mod language;
 pub mod madhab;
 pub mod method;
 pub mod parameters;
 pub mod polar_circle_resolution;
 pub mod prayer;
+pub mod prayer_category;
 pub mod prayer_time;
+pub mod rounding;
 pub mod twilight;
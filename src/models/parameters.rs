@@ -1,24 +1,114 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
 use super::adjustments::TimeAdjustment;
+use super::custom_method::{CustomMethod, CustomMethodError};
+use super::fajr_floor::FajrFloor;
+use super::forbidden_durations::ForbiddenDurations;
 use super::high_latitude_rule::HighLatitudeRule;
+use super::iqamah::IqamahOffsets;
 use super::madhab::Madhab;
 use super::method::Method;
 use super::polar_circle_resolution::PolarCircleResolution;
 use super::prayer::Prayer;
+use super::rounding::Rounding;
 use super::twilight::Twilight;
+use crate::astronomy::ops;
+use crate::astronomy::unit::Coordinates;
+use crate::constants::{
+    is_high_latitude, HIGH_LATITUDE_THRESHOLD, NAUTICAL_TWILIGHT_ANGLE,
+    SCALED_SEVENTH_REFERENCE_ANGLE,
+};
+
+fn default_imsak_interval() -> i32 {
+    10
+}
+
+fn default_duha_interval() -> i32 {
+    15
+}
+
+fn default_high_latitude_threshold() -> f64 {
+    HIGH_LATITUDE_THRESHOLD
+}
+
+fn default_use_equation_of_time() -> bool {
+    true
+}
 
 /// Parameters defines useful information to calculate prayer times.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parameters {
     pub method: Method,
     pub fajr_angle: f64,
+    /// Minutes before sunrise used to calculate Fajr, instead of the Fajr angle
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fajr_interval: i32,
     pub isha_angle: f64,
     pub isha_interval: i32,
+    /// Whether Isha is delayed during Ramadan, this only applies to methods
+    /// using a Ramadan specific Isha interval (Umm al-Qura and Qatar),
+    /// and requires the `hijri` feature
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ramadan: bool,
+    /// Whether the middle of the night is the local solar midnight (halfway between
+    /// two transits) instead of halfway between Maghrib and Fajr, the last third
+    /// of the night is then measured from the solar midnight to Fajr
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub solar_midnight: bool,
+    /// Angle of the sun below the horizon used to calculate Maghrib,
+    /// when it's not set, Maghrib is at sunset
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub maghrib_angle: Option<f64>,
+    /// Minutes before Fajr when eating must stop during Ramadan
+    #[cfg_attr(feature = "serde", serde(default = "default_imsak_interval"))]
+    pub imsak_interval: i32,
+    /// Minutes after sunrise when Duha starts
+    #[cfg_attr(feature = "serde", serde(default = "default_duha_interval"))]
+    pub duha_interval: i32,
     pub madhab: Madhab,
+    /// Shadow length ratio used to calculate Asr,
+    /// when it's not set (0), the madhab's ratio is used
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub asr_shadow_ratio: f64,
     pub twilight: Twilight,
     pub high_latitude_rule: HighLatitudeRule,
+    /// Latitude from which the high latitude rule is applied
+    #[cfg_attr(feature = "serde", serde(default = "default_high_latitude_threshold"))]
+    pub high_latitude_threshold: f64,
+    /// Where Fajr is bounded by the night portion of the high latitude rule
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fajr_floor: FajrFloor,
     pub polar_circle_resolution: PolarCircleResolution,
     pub adjustments: TimeAdjustment,
     pub method_adjustments: TimeAdjustment,
+    /// Rounding applied to computed prayer times
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rounding: Rounding,
+    /// Minutes between the adhan and the iqamah of each prayer
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub iqamah_offsets: IqamahOffsets,
+    /// Minutes of the windows during which voluntary prayers are disliked
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub forbidden_durations: ForbiddenDurations,
+    /// Seconds added to the sun transit to calculate Dhuhr, which prevents praying
+    /// at the instant of zenith (a forbidden time). When it's not set,
+    /// the method's Dhuhr adjustment is used
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dhuhr_after_zenith_seconds: Option<i64>,
+    /// Atmospheric pressure (in hPa) used to calculate the refraction at sunrise and sunset,
+    /// when it's not set, the standard pressure (1010 hPa) is used
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pressure_hpa: Option<f64>,
+    /// Temperature (in °C) used to calculate the refraction at sunrise and sunset,
+    /// when it's not set, the standard temperature (10°C) is used
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub temperature_c: Option<f64>,
+    /// Whether times follow the true sun (the default), or the mean sun without
+    /// the equation of time correction, which shifts times by up to 16 minutes
+    #[cfg_attr(feature = "serde", serde(default = "default_use_equation_of_time"))]
+    pub use_equation_of_time: bool,
 }
 
 impl Parameters {
@@ -27,21 +117,142 @@ impl Parameters {
             fajr_angle,
             isha_angle,
             method: Method::Other,
+            fajr_interval: 0,
             isha_interval: 0,
+            ramadan: false,
+            solar_midnight: false,
+            maghrib_angle: None,
+            imsak_interval: default_imsak_interval(),
+            duha_interval: default_duha_interval(),
             madhab: Madhab::Shafi,
+            asr_shadow_ratio: 0.0,
             twilight: Twilight::Red,
             high_latitude_rule: HighLatitudeRule::default(),
-            polar_circle_resolution: PolarCircleResolution::Unresolved,
+            high_latitude_threshold: default_high_latitude_threshold(),
+            fajr_floor: FajrFloor::default(),
+            polar_circle_resolution: PolarCircleResolution::default(),
             adjustments: TimeAdjustment::default(),
             method_adjustments: TimeAdjustment::default(),
+            rounding: Rounding::default(),
+            iqamah_offsets: IqamahOffsets::default(),
+            forbidden_durations: ForbiddenDurations::default(),
+            dhuhr_after_zenith_seconds: None,
+            pressure_hpa: None,
+            temperature_c: None,
+            use_equation_of_time: default_use_equation_of_time(),
+        }
+    }
+
+    /// Returns sensible parameters for the given location: the Moonsighting Committee method
+    /// at high latitudes (above 55° north or south), the Muslim World League method otherwise,
+    /// along with the recommended high latitude rule and the default `high_latitude_threshold`
+    pub fn recommended(coordinates: Coordinates) -> Parameters {
+        let method = if is_high_latitude(coordinates, Some(Method::MoonsightingCommittee)) {
+            Method::MoonsightingCommittee
+        } else {
+            Method::MuslimWorldLeague
+        };
+
+        ParametersBuilder::from_method(method, Madhab::Shafi)
+            .high_latitude_rule(HighLatitudeRule::recommended(coordinates))
+            .build()
+    }
+
+    /// Returns the parameters of the given custom method, once validated
+    pub fn from_custom(
+        custom_method: &CustomMethod,
+        madhab: Madhab,
+    ) -> Result<Parameters, CustomMethodError> {
+        custom_method.validate()?;
+
+        let mut builder = ParametersBuilder::new(
+            custom_method.fajr_angle,
+            custom_method.isha_angle.unwrap_or(0.0),
+        );
+        if let Some(isha_interval) = custom_method.isha_interval {
+            builder.isha_interval(isha_interval);
+        }
+
+        Ok(builder
+            .madhab(madhab)
+            .method_adjustments(custom_method.adjustments)
+            .high_latitude_rule(custom_method.high_latitude_rule)
+            .twilight(custom_method.twilight)
+            .build())
+    }
+
+    /// Checks that the parameters are consistent, e.g. Isha is defined either
+    /// by an angle or by an interval
+    pub fn validate(&self) -> Result<(), ParametersError> {
+        if self.fajr_angle != 0.0 && self.fajr_interval != 0 {
+            return Err(ParametersError::ConflictingFajr);
+        }
+        if self.isha_angle != 0.0 && self.isha_interval != 0 {
+            return Err(ParametersError::ConflictingIsha);
+        }
+        if self.isha_interval < 0 {
+            return Err(ParametersError::InvalidIshaInterval(self.isha_interval));
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the high latitude rule should be applied at the given location,
+    /// in both hemispheres
+    pub fn is_high_latitude(&self, coordinates: Coordinates) -> bool {
+        coordinates.latitude.abs() >= self.high_latitude_threshold
+    }
+
+    /// Returns true if Fajr should be bounded by the night portion at the given location
+    pub fn has_fajr_floor(&self, coordinates: Coordinates) -> bool {
+        match self.fajr_floor {
+            FajrFloor::None => false,
+            FajrFloor::HighLatitudeOnly => self.is_high_latitude(coordinates),
+            FajrFloor::Always => true,
         }
     }
 
-    pub fn night_portions(&self) -> (f64, f64) {
+    /// Returns the shadow length ratio used to calculate Asr
+    pub fn shadow_length_ratio(&self) -> f64 {
+        if self.asr_shadow_ratio > 0.0 {
+            self.asr_shadow_ratio
+        } else {
+            self.madhab.shadow_length_ratio().into()
+        }
+    }
+
+    /// Returns the minutes between the adhan and the iqamah of the given prayer,
+    /// if the prayer has an iqamah
+    pub fn iqamah_offset(&self, prayer: Prayer) -> Option<i64> {
+        match prayer {
+            Prayer::Fajr | Prayer::FajrTomorrow => Some(self.iqamah_offsets.fajr),
+            Prayer::Dhuhr => Some(self.iqamah_offsets.dhuhr),
+            Prayer::Asr => Some(self.iqamah_offsets.asr),
+            Prayer::Maghrib => Some(self.iqamah_offsets.maghrib),
+            Prayer::Isha => Some(self.iqamah_offsets.isha),
+            _ => None,
+        }
+    }
+
+    /// Returns the portions of the night used to bound Fajr and Isha at the given location
+    pub fn night_portions(&self, coordinates: Coordinates) -> (f64, f64) {
         match self.high_latitude_rule {
             HighLatitudeRule::MiddleOfTheNight => (1.0 / 2.0, 1.0 / 2.0),
             HighLatitudeRule::SeventhOfTheNight => (1.0 / 7.0, 1.0 / 7.0),
             HighLatitudeRule::TwilightAngle => (self.fajr_angle / 60.0, self.isha_angle / 60.0),
+            HighLatitudeRule::AngleBased => (
+                ops::twilight_night_portion(self.fajr_angle, coordinates.latitude),
+                ops::twilight_night_portion(self.isha_angle, coordinates.latitude),
+            ),
+            HighLatitudeRule::ScaledSeventhOfTheNight => (
+                self.fajr_angle / SCALED_SEVENTH_REFERENCE_ANGLE / 7.0,
+                self.isha_angle / SCALED_SEVENTH_REFERENCE_ANGLE / 7.0,
+            ),
+            HighLatitudeRule::NauticalTwilight => {
+                let portion =
+                    ops::twilight_night_portion(NAUTICAL_TWILIGHT_ANGLE, coordinates.latitude);
+                (portion, portion)
+            }
         }
     }
 
@@ -53,9 +264,93 @@ impl Parameters {
             Prayer::Asr => self.adjustments.asr + self.method_adjustments.asr,
             Prayer::Maghrib => self.adjustments.maghrib + self.method_adjustments.maghrib,
             Prayer::Isha => self.adjustments.isha + self.method_adjustments.isha,
+            Prayer::MiddleOfTheNight => {
+                self.adjustments.middle_of_the_night + self.method_adjustments.middle_of_the_night
+            }
+            Prayer::Qiyam => self.adjustments.qiyam + self.method_adjustments.qiyam,
             _ => 0,
         }
     }
+
+    /// Returns the seconds between the sun transit and Dhuhr,
+    /// including the user's Dhuhr adjustment
+    pub fn dhuhr_offset_seconds(&self) -> i64 {
+        let after_zenith = self
+            .dhuhr_after_zenith_seconds
+            .unwrap_or(self.method_adjustments.dhuhr * 60);
+
+        after_zenith + self.adjustments.dhuhr * 60
+    }
+}
+
+/// Error returned when the parameters are inconsistent
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ParametersError {
+    /// Both the Fajr angle and the Fajr interval are set
+    ConflictingFajr,
+    /// Both the Isha angle and the Isha interval are set
+    ConflictingIsha,
+    /// The Isha interval is negative, which would place Isha before Maghrib
+    InvalidIshaInterval(i32),
+}
+
+impl fmt::Display for ParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParametersError::ConflictingFajr => {
+                write!(f, "Fajr angle and Fajr interval can't be used together")
+            }
+            ParametersError::ConflictingIsha => {
+                write!(f, "Isha angle and Isha interval can't be used together")
+            }
+            ParametersError::InvalidIshaInterval(isha_interval) => {
+                write!(f, "Isha interval must be positive, got {}", isha_interval)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParametersError {}
+
+/// Angles and ratios are hashed by their bit pattern, as they're compared exactly
+impl Hash for Parameters {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Adding zero normalizes -0.0, which is equal to 0.0
+        let hash_f64 = |value: f64, state: &mut H| (value + 0.0).to_bits().hash(state);
+
+        self.method.hash(state);
+        hash_f64(self.fajr_angle, state);
+        self.fajr_interval.hash(state);
+        hash_f64(self.isha_angle, state);
+        self.isha_interval.hash(state);
+        self.ramadan.hash(state);
+        self.solar_midnight.hash(state);
+        self.maghrib_angle
+            .map(|angle| (angle + 0.0).to_bits())
+            .hash(state);
+        self.imsak_interval.hash(state);
+        self.duha_interval.hash(state);
+        self.madhab.hash(state);
+        hash_f64(self.asr_shadow_ratio, state);
+        self.twilight.hash(state);
+        self.high_latitude_rule.hash(state);
+        hash_f64(self.high_latitude_threshold, state);
+        self.fajr_floor.hash(state);
+        self.polar_circle_resolution.hash(state);
+        self.adjustments.hash(state);
+        self.method_adjustments.hash(state);
+        self.rounding.hash(state);
+        self.iqamah_offsets.hash(state);
+        self.forbidden_durations.hash(state);
+        self.dhuhr_after_zenith_seconds.hash(state);
+        self.pressure_hpa
+            .map(|pressure| (pressure + 0.0).to_bits())
+            .hash(state);
+        self.temperature_c
+            .map(|temperature| (temperature + 0.0).to_bits())
+            .hash(state);
+        self.use_equation_of_time.hash(state);
+    }
 }
 
 /// A builder for the the [Parameters](struct.Parameters.html).
@@ -64,14 +359,30 @@ impl Parameters {
 pub struct ParametersBuilder {
     method: Method,
     fajr_angle: f64,
+    fajr_interval: i32,
     isha_angle: f64,
     isha_interval: i32,
+    ramadan: bool,
+    solar_midnight: bool,
+    maghrib_angle: Option<f64>,
+    imsak_interval: i32,
+    duha_interval: i32,
     madhab: Madhab,
+    asr_shadow_ratio: f64,
     pub twilight: Twilight,
     pub high_latitude_rule: HighLatitudeRule,
+    high_latitude_threshold: f64,
+    fajr_floor: FajrFloor,
     pub polar_circle_resolution: PolarCircleResolution,
     adjustments: TimeAdjustment,
     method_adjustments: TimeAdjustment,
+    rounding: Rounding,
+    iqamah_offsets: IqamahOffsets,
+    forbidden_durations: ForbiddenDurations,
+    dhuhr_after_zenith_seconds: Option<i64>,
+    pressure_hpa: Option<f64>,
+    temperature_c: Option<f64>,
+    use_equation_of_time: bool,
 }
 
 impl ParametersBuilder {
@@ -80,28 +391,80 @@ impl ParametersBuilder {
             fajr_angle,
             isha_angle,
             method: Method::Other,
+            fajr_interval: 0,
             isha_interval: 0,
+            ramadan: false,
+            solar_midnight: false,
+            maghrib_angle: None,
+            imsak_interval: default_imsak_interval(),
+            duha_interval: default_duha_interval(),
             madhab: Madhab::Shafi,
+            asr_shadow_ratio: 0.0,
             twilight: Twilight::Red,
             high_latitude_rule: HighLatitudeRule::MiddleOfTheNight,
-            polar_circle_resolution: PolarCircleResolution::Unresolved,
+            high_latitude_threshold: default_high_latitude_threshold(),
+            fajr_floor: FajrFloor::default(),
+            polar_circle_resolution: PolarCircleResolution::default(),
             adjustments: TimeAdjustment::default(),
             method_adjustments: TimeAdjustment::default(),
+            rounding: Rounding::default(),
+            iqamah_offsets: IqamahOffsets::default(),
+            forbidden_durations: ForbiddenDurations::default(),
+            dhuhr_after_zenith_seconds: None,
+            pressure_hpa: None,
+            temperature_c: None,
+            use_equation_of_time: default_use_equation_of_time(),
         }
     }
 
+    /// Returns the parameters of the given method
+    ///
+    /// `Method::Other` has no angles, use [with_angles](#method.with_angles) instead.
     pub fn with(method: Method, madhab: Madhab) -> Parameters {
+        debug_assert!(
+            method != Method::Other,
+            "Method::Other has no angles, use ParametersBuilder::with_angles instead"
+        );
+
         let mut params = method.parameters();
         params.madhab = madhab;
 
         params
     }
 
+    /// Returns a builder initialized with the parameters of the given method,
+    /// so that some of them (e.g. the Fajr angle) can be overridden
+    /// while keeping the method's adjustments
+    pub fn from_method(method: Method, madhab: Madhab) -> ParametersBuilder {
+        let mut builder = ParametersBuilder::from(method.parameters());
+        builder.madhab(madhab);
+
+        builder
+    }
+
+    /// Returns the parameters of the given method using the given Fajr and Isha angles
+    pub fn with_angles(
+        method: Method,
+        madhab: Madhab,
+        fajr_angle: f64,
+        isha_angle: f64,
+    ) -> Parameters {
+        ParametersBuilder::from_method(method, madhab)
+            .fajr_angle(fajr_angle)
+            .isha_angle(isha_angle)
+            .build()
+    }
+
     pub fn method(&mut self, method: Method) -> &mut ParametersBuilder {
         self.method = method;
         self
     }
 
+    pub fn adjustments(&mut self, adjustments: TimeAdjustment) -> &mut ParametersBuilder {
+        self.adjustments = adjustments;
+        self
+    }
+
     pub fn method_adjustments(
         &mut self,
         method_adjustments: TimeAdjustment,
@@ -110,6 +473,11 @@ impl ParametersBuilder {
         self
     }
 
+    pub fn fajr_floor(&mut self, fajr_floor: FajrFloor) -> &mut ParametersBuilder {
+        self.fajr_floor = fajr_floor;
+        self
+    }
+
     pub fn polar_circle_resolution(
         &mut self,
         polar_circle_resolution: PolarCircleResolution,
@@ -126,34 +494,183 @@ impl ParametersBuilder {
         self
     }
 
+    pub fn high_latitude_threshold(
+        &mut self,
+        high_latitude_threshold: f64,
+    ) -> &mut ParametersBuilder {
+        self.high_latitude_threshold = high_latitude_threshold;
+        self
+    }
+
     pub fn madhab(&mut self, madhab: Madhab) -> &mut ParametersBuilder {
         self.madhab = madhab;
         self
     }
 
+    pub fn asr_shadow_ratio(&mut self, asr_shadow_ratio: f64) -> &mut ParametersBuilder {
+        self.asr_shadow_ratio = asr_shadow_ratio;
+        self
+    }
+
+    pub fn iqamah_offsets(&mut self, iqamah_offsets: IqamahOffsets) -> &mut ParametersBuilder {
+        self.iqamah_offsets = iqamah_offsets;
+        self
+    }
+
+    pub fn forbidden_durations(
+        &mut self,
+        forbidden_durations: ForbiddenDurations,
+    ) -> &mut ParametersBuilder {
+        self.forbidden_durations = forbidden_durations;
+        self
+    }
+
+    pub fn dhuhr_after_zenith_seconds(&mut self, seconds: i64) -> &mut ParametersBuilder {
+        self.dhuhr_after_zenith_seconds = Some(seconds);
+        self
+    }
+
+    pub fn atmosphere(&mut self, pressure_hpa: f64, temperature_c: f64) -> &mut ParametersBuilder {
+        self.pressure_hpa = Some(pressure_hpa);
+        self.temperature_c = Some(temperature_c);
+        self
+    }
+
+    pub fn use_equation_of_time(&mut self, use_equation_of_time: bool) -> &mut ParametersBuilder {
+        self.use_equation_of_time = use_equation_of_time;
+        self
+    }
+
+    pub fn rounding(&mut self, rounding: Rounding) -> &mut ParametersBuilder {
+        self.rounding = rounding;
+        self
+    }
+
     pub fn twilight(&mut self, twilight: Twilight) -> &mut ParametersBuilder {
         self.twilight = twilight;
         self
     }
 
+    pub fn fajr_angle(&mut self, fajr_angle: f64) -> &mut ParametersBuilder {
+        self.fajr_interval = 0;
+        self.fajr_angle = fajr_angle;
+        self
+    }
+
+    pub fn fajr_interval(&mut self, fajr_interval: i32) -> &mut ParametersBuilder {
+        self.fajr_angle = 0.0;
+        self.fajr_interval = fajr_interval;
+        self
+    }
+
+    pub fn isha_angle(&mut self, isha_angle: f64) -> &mut ParametersBuilder {
+        self.isha_angle = isha_angle;
+        self.isha_interval = 0;
+        self
+    }
+
     pub fn isha_interval(&mut self, isha_interval: i32) -> &mut ParametersBuilder {
         self.isha_angle = 0.0;
         self.isha_interval = isha_interval;
         self
     }
 
+    pub fn ramadan(&mut self, ramadan: bool) -> &mut ParametersBuilder {
+        self.ramadan = ramadan;
+        self
+    }
+
+    pub fn solar_midnight(&mut self, solar_midnight: bool) -> &mut ParametersBuilder {
+        self.solar_midnight = solar_midnight;
+        self
+    }
+
+    pub fn maghrib_angle(&mut self, maghrib_angle: f64) -> &mut ParametersBuilder {
+        self.maghrib_angle = Some(maghrib_angle);
+        self
+    }
+
+    pub fn imsak_interval(&mut self, imsak_interval: i32) -> &mut ParametersBuilder {
+        self.imsak_interval = imsak_interval;
+        self
+    }
+
+    pub fn duha_interval(&mut self, duha_interval: i32) -> &mut ParametersBuilder {
+        self.duha_interval = duha_interval;
+        self
+    }
+
+    /// Builds the parameters once validated, see [Parameters::validate](struct.Parameters.html#method.validate)
+    pub fn try_build(&self) -> Result<Parameters, ParametersError> {
+        let parameters = self.build();
+        parameters.validate()?;
+
+        Ok(parameters)
+    }
+
     pub fn build(&self) -> Parameters {
         Parameters {
             fajr_angle: self.fajr_angle,
+            fajr_interval: self.fajr_interval,
             isha_angle: self.isha_angle,
             method: self.method,
             isha_interval: self.isha_interval,
+            ramadan: self.ramadan,
+            solar_midnight: self.solar_midnight,
+            maghrib_angle: self.maghrib_angle,
+            imsak_interval: self.imsak_interval,
+            duha_interval: self.duha_interval,
             madhab: self.madhab,
+            asr_shadow_ratio: self.asr_shadow_ratio,
             twilight: self.twilight,
             high_latitude_rule: self.high_latitude_rule,
+            high_latitude_threshold: self.high_latitude_threshold,
+            fajr_floor: self.fajr_floor,
             polar_circle_resolution: self.polar_circle_resolution,
             adjustments: self.adjustments,
             method_adjustments: self.method_adjustments,
+            rounding: self.rounding,
+            iqamah_offsets: self.iqamah_offsets,
+            forbidden_durations: self.forbidden_durations,
+            dhuhr_after_zenith_seconds: self.dhuhr_after_zenith_seconds,
+            pressure_hpa: self.pressure_hpa,
+            temperature_c: self.temperature_c,
+            use_equation_of_time: self.use_equation_of_time,
+        }
+    }
+}
+
+impl From<Parameters> for ParametersBuilder {
+    /// Returns a builder initialized with the given parameters,
+    /// useful to tweak a preset method
+    fn from(parameters: Parameters) -> Self {
+        ParametersBuilder {
+            fajr_angle: parameters.fajr_angle,
+            fajr_interval: parameters.fajr_interval,
+            isha_angle: parameters.isha_angle,
+            method: parameters.method,
+            isha_interval: parameters.isha_interval,
+            ramadan: parameters.ramadan,
+            solar_midnight: parameters.solar_midnight,
+            maghrib_angle: parameters.maghrib_angle,
+            imsak_interval: parameters.imsak_interval,
+            duha_interval: parameters.duha_interval,
+            madhab: parameters.madhab,
+            asr_shadow_ratio: parameters.asr_shadow_ratio,
+            twilight: parameters.twilight,
+            high_latitude_rule: parameters.high_latitude_rule,
+            high_latitude_threshold: parameters.high_latitude_threshold,
+            fajr_floor: parameters.fajr_floor,
+            polar_circle_resolution: parameters.polar_circle_resolution,
+            adjustments: parameters.adjustments,
+            method_adjustments: parameters.method_adjustments,
+            rounding: parameters.rounding,
+            iqamah_offsets: parameters.iqamah_offsets,
+            forbidden_durations: parameters.forbidden_durations,
+            dhuhr_after_zenith_seconds: parameters.dhuhr_after_zenith_seconds,
+            pressure_hpa: parameters.pressure_hpa,
+            temperature_c: parameters.temperature_c,
+            use_equation_of_time: parameters.use_equation_of_time,
         }
     }
 }
@@ -161,6 +678,120 @@ impl ParametersBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::adjustments::TimeAdjustmentBuilder;
+
+    #[test]
+    fn dhuhr_after_zenith_overrides_method_adjustment() {
+        let mut builder = ParametersBuilder::from_method(Method::Karachi, Madhab::Shafi);
+        assert_eq!(builder.build().dhuhr_offset_seconds(), 60);
+
+        let params = builder
+            .dhuhr_after_zenith_seconds(150)
+            .adjustments(TimeAdjustmentBuilder::new().dhuhr(2).build())
+            .build();
+        assert_eq!(params.dhuhr_offset_seconds(), 270);
+    }
+
+    #[test]
+    fn recommended_parameters_at_mid_latitude() {
+        let params = Parameters::recommended(Coordinates::new(36.8065, 10.1815));
+
+        assert_eq!(params.method, Method::MuslimWorldLeague);
+        assert_eq!(params.madhab, Madhab::Shafi);
+        assert_eq!(
+            params.high_latitude_rule,
+            HighLatitudeRule::MiddleOfTheNight
+        );
+    }
+
+    #[test]
+    fn recommended_parameters_at_high_latitude() {
+        let params = Parameters::recommended(Coordinates::new(59.9139, 10.7522));
+
+        assert_eq!(params.method, Method::MoonsightingCommittee);
+        assert_eq!(params.fajr_angle, 18.0);
+        assert_eq!(
+            params.high_latitude_rule,
+            HighLatitudeRule::SeventhOfTheNight
+        );
+    }
+
+    #[test]
+    fn last_isha_setter_wins() {
+        let params = ParametersBuilder::new(18.0, 17.0)
+            .isha_interval(90)
+            .isha_angle(18.5)
+            .try_build()
+            .unwrap();
+        assert_eq!(params.isha_angle, 18.5);
+        assert_eq!(params.isha_interval, 0);
+
+        let params = ParametersBuilder::new(18.0, 17.0)
+            .isha_angle(18.5)
+            .isha_interval(90)
+            .try_build()
+            .unwrap();
+        assert_eq!(params.isha_angle, 0.0);
+        assert_eq!(params.isha_interval, 90);
+    }
+
+    #[test]
+    fn last_fajr_setter_wins() {
+        let params = ParametersBuilder::new(18.0, 17.0)
+            .fajr_interval(75)
+            .try_build()
+            .unwrap();
+        assert_eq!(params.fajr_angle, 0.0);
+        assert_eq!(params.fajr_interval, 75);
+
+        let mut params = ParametersBuilder::from(params).fajr_angle(18.5).build();
+        assert_eq!(params.fajr_interval, 0);
+
+        params.fajr_interval = 75;
+        assert_eq!(params.validate(), Err(ParametersError::ConflictingFajr));
+    }
+
+    #[test]
+    fn reject_conflicting_isha() {
+        let mut params = ParametersBuilder::with(Method::UmmAlQura, Madhab::Shafi);
+        assert_eq!(params.validate(), Ok(()));
+
+        params.isha_angle = 17.0;
+        assert_eq!(params.validate(), Err(ParametersError::ConflictingIsha));
+        assert_eq!(
+            ParametersBuilder::from(params).try_build(),
+            Err(ParametersError::ConflictingIsha)
+        );
+    }
+
+    #[test]
+    fn reject_negative_isha_interval() {
+        let mut builder = ParametersBuilder::from_method(Method::Qatar, Madhab::Shafi);
+        builder.isha_interval(-30);
+
+        assert_eq!(
+            builder.try_build(),
+            Err(ParametersError::InvalidIshaInterval(-30))
+        );
+        assert_eq!(
+            ParametersError::InvalidIshaInterval(-30).to_string(),
+            "Isha interval must be positive, got -30"
+        );
+        assert!(builder.isha_interval(90).try_build().is_ok());
+    }
+
+    #[test]
+    fn override_method_fajr_angle() {
+        let params = ParametersBuilder::from_method(Method::Karachi, Madhab::Hanafi)
+            .fajr_angle(16.0)
+            .build();
+
+        assert_eq!(params.method, Method::Karachi);
+        assert_eq!(params.madhab, Madhab::Hanafi);
+        assert_eq!(params.fajr_angle, 16.0);
+        assert_eq!(params.isha_angle, 18.0);
+        assert_eq!(params.method_adjustments.dhuhr, 1);
+    }
 
     #[test]
     fn calculate_parameters_with_fajr_and_isha_angles() {
@@ -169,14 +800,54 @@ mod tests {
         assert_eq!(params.fajr_angle, 18.0);
         assert_eq!(params.isha_angle, 18.0);
         assert_eq!(params.isha_interval, 0);
+        assert_eq!(params.imsak_interval, 10);
+        assert_eq!(params.duha_interval, 15);
+    }
+
+    #[test]
+    fn custom_asr_shadow_ratio_overrides_madhab() {
+        let mut params = Parameters::new(18.0, 18.0);
+        assert_eq!(params.shadow_length_ratio(), 1.0);
+
+        params.madhab = Madhab::Hanafi;
+        assert_eq!(params.shadow_length_ratio(), 2.0);
+
+        let params = ParametersBuilder::new(18.0, 18.0)
+            .asr_shadow_ratio(1.5)
+            .build();
+        assert_eq!(params.shadow_length_ratio(), 1.5);
+    }
+
+    #[test]
+    fn user_and_method_adjustments_are_summed() {
+        let params = ParametersBuilder::new(18.0, 17.0)
+            .adjustments(TimeAdjustment::new(2, 0, 1, 0, -3, 5))
+            .method_adjustments(TimeAdjustment::new(-1, 0, 4, 0, 3, 0))
+            .build();
+
+        assert_eq!(params.time_adjustments(Prayer::Fajr), 1);
+        assert_eq!(params.time_adjustments(Prayer::Sunrise), 0);
+        assert_eq!(params.time_adjustments(Prayer::Dhuhr), 5);
+        assert_eq!(params.time_adjustments(Prayer::Maghrib), 0);
+        assert_eq!(params.time_adjustments(Prayer::Isha), 5);
     }
 
     #[test]
     fn calculated_night_portions_default_to_twilight_angle() {
         let params = Parameters::new(18.0, 18.0);
 
-        assert_eq!(params.night_portions().0, 18. / 60.);
-        assert_eq!(params.night_portions().1, 18. / 60.);
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .0,
+            18. / 60.
+        );
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .1,
+            18. / 60.
+        );
     }
 
     #[test]
@@ -185,8 +856,18 @@ mod tests {
             .high_latitude_rule(HighLatitudeRule::MiddleOfTheNight)
             .build();
 
-        assert_eq!(params.night_portions().0, 1. / 2.);
-        assert_eq!(params.night_portions().1, 1. / 2.);
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .0,
+            1. / 2.
+        );
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .1,
+            1. / 2.
+        );
     }
 
     #[test]
@@ -195,8 +876,18 @@ mod tests {
             .high_latitude_rule(HighLatitudeRule::SeventhOfTheNight)
             .build();
 
-        assert_eq!(params.night_portions().0, 1.0 / 7.0);
-        assert_eq!(params.night_portions().1, 1.0 / 7.0);
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .0,
+            1.0 / 7.0
+        );
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .1,
+            1.0 / 7.0
+        );
     }
 
     #[test]
@@ -205,8 +896,84 @@ mod tests {
             .high_latitude_rule(HighLatitudeRule::TwilightAngle)
             .build();
 
-        assert_eq!(params.night_portions().0, 10.0 / 60.0);
-        assert_eq!(params.night_portions().1, 15.0 / 60.0);
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .0,
+            10.0 / 60.0
+        );
+        assert_eq!(
+            params
+                .night_portions(Coordinates::new(21.4225241, 39.8261818))
+                .1,
+            15.0 / 60.0
+        );
+    }
+
+    #[test]
+    fn calculated_night_portions_angle_based() {
+        let params = ParametersBuilder::new(18.0, 17.0)
+            .high_latitude_rule(HighLatitudeRule::AngleBased)
+            .build();
+        let equator = params.night_portions(Coordinates::new(0.0, 0.0));
+        let newcastle = params.night_portions(Coordinates::new(55.0, -1.6));
+
+        assert!((equator.0 - 0.1).abs() < 1e-9);
+        assert!(equator.1 < equator.0);
+        assert!(newcastle.0 > equator.0);
+        assert!(newcastle.0 > 1.0 / 7.0);
+    }
+
+    #[test]
+    fn calculated_night_portions_nautical_twilight() {
+        let params = ParametersBuilder::new(18.0, 17.0)
+            .high_latitude_rule(HighLatitudeRule::NauticalTwilight)
+            .build();
+        let (fajr_portion, isha_portion) = params.night_portions(Coordinates::new(0.0, 0.0));
+
+        assert!((fajr_portion - 12.0 / 180.0).abs() < 1e-9);
+        assert_eq!(fajr_portion, isha_portion);
+    }
+
+    #[test]
+    fn calculated_night_portions_scaled_seventh_of_the_night() {
+        let coordinates = Coordinates::new(55.0, -1.6);
+        let seventh = ParametersBuilder::new(18.0, 18.0)
+            .high_latitude_rule(HighLatitudeRule::SeventhOfTheNight)
+            .build();
+        let scaled = ParametersBuilder::new(18.0, 18.0)
+            .high_latitude_rule(HighLatitudeRule::ScaledSeventhOfTheNight)
+            .build();
+
+        assert_eq!(
+            scaled.night_portions(coordinates),
+            seventh.night_portions(coordinates)
+        );
+
+        let (fajr_portion, isha_portion) = ParametersBuilder::new(15.0, 12.0)
+            .high_latitude_rule(HighLatitudeRule::ScaledSeventhOfTheNight)
+            .build()
+            .night_portions(coordinates);
+        assert!((fajr_portion - 15.0 / 18.0 / 7.0).abs() < 1e-9);
+        assert!((isha_portion - 12.0 / 18.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parameters_using_method_and_angles() {
+        let params = ParametersBuilder::with_angles(Method::Other, Madhab::Hanafi, 18.0, 17.0);
+
+        assert_eq!(params.method, Method::Other);
+        assert_eq!(params.madhab, Madhab::Hanafi);
+        assert_eq!(params.fajr_angle, 18.0);
+        assert_eq!(params.isha_angle, 17.0);
+        assert_eq!(params.isha_interval, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn parameters_using_other_method_without_angles() {
+        ParametersBuilder::with(Method::Other, Madhab::Shafi);
     }
 
     #[test]
@@ -219,4 +986,63 @@ mod tests {
         assert_eq!(params.isha_interval, 0);
         assert_eq!(params.madhab, Madhab::Hanafi);
     }
+
+    #[test]
+    fn override_angles_of_a_preset_method() {
+        let preset = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        let params = ParametersBuilder::from(preset).fajr_angle(19.5).build();
+
+        assert_eq!(params.method, Method::MuslimWorldLeague);
+        assert_eq!(params.fajr_angle, 19.5);
+        assert_eq!(params.isha_angle, 17.0);
+        assert_eq!(params.method_adjustments, preset.method_adjustments);
+    }
+
+    #[test]
+    fn isha_angle_clears_isha_interval() {
+        let preset = ParametersBuilder::with(Method::UmmAlQura, Madhab::Shafi);
+        let params = ParametersBuilder::from(preset).isha_angle(18.0).build();
+
+        assert_eq!(params.isha_angle, 18.0);
+        assert_eq!(params.isha_interval, 0);
+
+        let params = ParametersBuilder::new(18.0, 18.0).isha_interval(90).build();
+
+        assert_eq!(params.isha_angle, 0.0);
+        assert_eq!(params.isha_interval, 90);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_parameters_from_json() {
+        let json = r#"{
+            "method": "muslim-world-league",
+            "fajr_angle": 18.0,
+            "isha_angle": 17.0,
+            "isha_interval": 0,
+            "madhab": "hanafi",
+            "twilight": "red",
+            "high_latitude_rule": "seventh-of-the-night",
+            "polar_circle_resolution": "unresolved",
+            "adjustments": {"fajr": 0, "sunrise": 0, "dhuhr": 0, "asr": 2, "maghrib": 0, "isha": 0},
+            "method_adjustments": {"fajr": 0, "sunrise": 0, "dhuhr": 1, "asr": 0, "maghrib": 0, "isha": 0}
+        }"#;
+        let params: Parameters = serde_json::from_str(json).unwrap();
+
+        assert_eq!(params.method, Method::MuslimWorldLeague);
+        assert_eq!(params.fajr_angle, 18.0);
+        assert_eq!(params.isha_angle, 17.0);
+        assert_eq!(params.madhab, Madhab::Hanafi);
+        assert_eq!(
+            params.high_latitude_rule,
+            HighLatitudeRule::SeventhOfTheNight
+        );
+        assert_eq!(
+            params.polar_circle_resolution,
+            PolarCircleResolution::Unresolved
+        );
+        assert_eq!(params.time_adjustments(Prayer::Asr), 2);
+        assert_eq!(params.time_adjustments(Prayer::Dhuhr), 1);
+        assert_eq!(params.imsak_interval, 10);
+    }
 }
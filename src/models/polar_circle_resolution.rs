@@ -1,6 +1,8 @@
 use clap::ValueEnum;
 
-#[derive(PartialEq, Debug, Copy, Clone, ValueEnum)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum PolarCircleResolution {
     NearestTown,
     NearestDay,
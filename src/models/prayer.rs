@@ -1,11 +1,19 @@
+use std::fmt;
+
 use chrono::{Datelike, Utc, Weekday};
+use clap::ValueEnum;
+
+use crate::models::language::Language;
+use crate::models::prayer_category::PrayerCategory;
 
 /// Names of all obligatory prayers,
 /// sunrise, and Qiyam.
-#[derive(PartialEq, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
 pub enum Prayer {
+    Imsak,
     Fajr,
     Sunrise,
+    Duha,
     Dhuhr,
     Asr,
     Maghrib,
@@ -16,34 +24,190 @@ pub enum Prayer {
 }
 
 impl Prayer {
-    pub fn name(&self) -> String {
+    /// Returns the day prayers (including sunrise and night times) in chronological order
+    pub fn all() -> [Prayer; 8] {
+        [
+            Prayer::Fajr,
+            Prayer::Sunrise,
+            Prayer::Dhuhr,
+            Prayer::Asr,
+            Prayer::Maghrib,
+            Prayer::Isha,
+            Prayer::MiddleOfTheNight,
+            Prayer::Qiyam,
+        ]
+    }
+
+    /// Returns the prayer following this one,
+    /// FajrTomorrow is followed by the sunrise of the next day.
+    pub fn next(&self) -> Prayer {
+        match self {
+            Prayer::Imsak => Prayer::Fajr,
+            Prayer::Fajr => Prayer::Sunrise,
+            Prayer::Sunrise => Prayer::Dhuhr,
+            Prayer::Duha => Prayer::Dhuhr,
+            Prayer::Dhuhr => Prayer::Asr,
+            Prayer::Asr => Prayer::Maghrib,
+            Prayer::Maghrib => Prayer::Isha,
+            Prayer::Isha => Prayer::MiddleOfTheNight,
+            Prayer::MiddleOfTheNight => Prayer::Qiyam,
+            Prayer::Qiyam => Prayer::FajrTomorrow,
+            Prayer::FajrTomorrow => Prayer::Sunrise,
+        }
+    }
+
+    /// Returns whether the prayer is obligatory, voluntary or a time marker
+    pub fn category(&self) -> PrayerCategory {
         match self {
-            Prayer::Fajr | Prayer::FajrTomorrow => String::from("Fajr"),
-            Prayer::Sunrise => String::from("Sunrise"),
-            Prayer::Dhuhr => {
-                if Utc::now().weekday() == Weekday::Fri {
-                    String::from("Jumua")
-                } else {
-                    String::from("Dhuhr")
-                }
-            }
-            Prayer::Asr => String::from("Asr"),
-            Prayer::Maghrib => String::from("Maghrib"),
-            Prayer::Isha => String::from("Isha"),
-            Prayer::MiddleOfTheNight => String::from("Middle Of The Night"),
-            Prayer::Qiyam => String::from("Qiyam"),
+            Prayer::Fajr
+            | Prayer::Dhuhr
+            | Prayer::Asr
+            | Prayer::Maghrib
+            | Prayer::Isha
+            | Prayer::FajrTomorrow => PrayerCategory::Obligatory,
+            Prayer::Imsak | Prayer::Sunrise => PrayerCategory::Marker,
+            Prayer::Duha | Prayer::MiddleOfTheNight | Prayer::Qiyam => PrayerCategory::Voluntary,
         }
     }
+
+    /// Returns true for the five obligatory (fard) prayers
+    pub fn is_fard(&self) -> bool {
+        self.category() == PrayerCategory::Obligatory
+    }
+
+    pub fn name(&self) -> String {
+        self.name_localized(Language::English)
+    }
+
+    /// Returns the prayer name for the given weekday, Dhuhr is named Jumua on Fridays
+    pub fn name_for(&self, weekday: Weekday) -> String {
+        self.name_on(weekday, Language::English)
+    }
+
+    /// Returns the prayer name in the given language, Dhuhr is named Jumua on Fridays
+    ///
+    /// The current weekday is used, prefer [PrayerTimes::name_localized](../../prayer_times/struct.PrayerTimes.html#method.name_localized)
+    /// to name the prayers of a computed day.
+    pub fn name_localized(&self, lang: Language) -> String {
+        self.name_on(Utc::now().weekday(), lang)
+    }
+
+    /// Parses a case-insensitive English prayer name, as displayed or as a command line value
+    /// (e.g. "Middle Of The Night" or "middle-of-the-night"), along with the aliases
+    /// "jumua" for Dhuhr and "midnight" for the middle of the night
+    pub fn from_name(name: &str) -> Option<Prayer> {
+        let name: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_lowercase();
+
+        match name.as_str() {
+            "imsak" => Some(Prayer::Imsak),
+            "fajr" => Some(Prayer::Fajr),
+            "sunrise" => Some(Prayer::Sunrise),
+            "duha" => Some(Prayer::Duha),
+            "dhuhr" | "jumua" => Some(Prayer::Dhuhr),
+            "asr" => Some(Prayer::Asr),
+            "maghrib" => Some(Prayer::Maghrib),
+            "isha" => Some(Prayer::Isha),
+            "middleofthenight" | "midnight" => Some(Prayer::MiddleOfTheNight),
+            "qiyam" => Some(Prayer::Qiyam),
+            "fajrtomorrow" => Some(Prayer::FajrTomorrow),
+            _ => None,
+        }
+    }
+
+    /// Returns the prayer name in the given language for the given weekday
+    pub fn name_on(&self, weekday: Weekday, lang: Language) -> String {
+        let is_friday = weekday == Weekday::Fri;
+
+        let name = match lang {
+            Language::English => match self {
+                Prayer::Imsak => "Imsak",
+                Prayer::Fajr | Prayer::FajrTomorrow => "Fajr",
+                Prayer::Sunrise => "Sunrise",
+                Prayer::Duha => "Duha",
+                Prayer::Dhuhr if is_friday => "Jumua",
+                Prayer::Dhuhr => "Dhuhr",
+                Prayer::Asr => "Asr",
+                Prayer::Maghrib => "Maghrib",
+                Prayer::Isha => "Isha",
+                Prayer::MiddleOfTheNight => "Middle Of The Night",
+                Prayer::Qiyam => "Qiyam",
+            },
+            Language::Arabic => match self {
+                Prayer::Imsak => "الإمساك",
+                Prayer::Fajr | Prayer::FajrTomorrow => "الفجر",
+                Prayer::Sunrise => "الشروق",
+                Prayer::Duha => "الضحى",
+                Prayer::Dhuhr if is_friday => "الجمعة",
+                Prayer::Dhuhr => "الظهر",
+                Prayer::Asr => "العصر",
+                Prayer::Maghrib => "المغرب",
+                Prayer::Isha => "العشاء",
+                Prayer::MiddleOfTheNight => "منتصف الليل",
+                Prayer::Qiyam => "قيام الليل",
+            },
+            Language::French => match self {
+                Prayer::Imsak => "Imsak",
+                Prayer::Fajr | Prayer::FajrTomorrow => "Fajr",
+                Prayer::Sunrise => "Lever du soleil",
+                Prayer::Duha => "Duha",
+                Prayer::Dhuhr if is_friday => "Joumoua",
+                Prayer::Dhuhr => "Dhuhr",
+                Prayer::Asr => "Asr",
+                Prayer::Maghrib => "Maghrib",
+                Prayer::Isha => "Isha",
+                Prayer::MiddleOfTheNight => "Milieu de la nuit",
+                Prayer::Qiyam => "Qiyam",
+            },
+        };
+
+        String::from(name)
+    }
+}
+
+impl fmt::Display for Prayer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn all_prayers_in_chronological_order() {
+        let prayers = Prayer::all();
+
+        assert_eq!(prayers.len(), 8);
+        assert_eq!(prayers[0], Prayer::Fajr);
+        assert_eq!(prayers[7], Prayer::Qiyam);
+    }
+
+    #[test]
+    fn next_prayer_of_each_prayer() {
+        assert_eq!(Prayer::Imsak.next(), Prayer::Fajr);
+        assert_eq!(Prayer::Fajr.next(), Prayer::Sunrise);
+        assert_eq!(Prayer::Sunrise.next(), Prayer::Dhuhr);
+        assert_eq!(Prayer::Duha.next(), Prayer::Dhuhr);
+        assert_eq!(Prayer::Dhuhr.next(), Prayer::Asr);
+        assert_eq!(Prayer::Asr.next(), Prayer::Maghrib);
+        assert_eq!(Prayer::Maghrib.next(), Prayer::Isha);
+        assert_eq!(Prayer::Isha.next(), Prayer::MiddleOfTheNight);
+        assert_eq!(Prayer::MiddleOfTheNight.next(), Prayer::Qiyam);
+        assert_eq!(Prayer::Qiyam.next(), Prayer::FajrTomorrow);
+        assert_eq!(Prayer::FajrTomorrow.next(), Prayer::Sunrise);
+    }
+
     #[test]
     fn prayer_name_for_fajr_en_transliteration() {
+        assert_eq!(Prayer::Imsak.name(), "Imsak");
         assert_eq!(Prayer::Fajr.name(), "Fajr");
         assert_eq!(Prayer::Sunrise.name(), "Sunrise");
+        assert_eq!(Prayer::Duha.name(), "Duha");
 
         if Utc::now().weekday() == Weekday::Fri {
             assert_eq!(Prayer::Dhuhr.name(), "Jumua");
@@ -57,4 +221,112 @@ mod tests {
         assert_eq!(Prayer::MiddleOfTheNight.name(), "Middle Of The Night");
         assert_eq!(Prayer::Qiyam.name(), "Qiyam");
     }
+
+    #[test]
+    fn dhuhr_name_on_friday() {
+        assert_eq!(
+            Prayer::Dhuhr.name_on(Weekday::Fri, Language::English),
+            "Jumua"
+        );
+        assert_eq!(
+            Prayer::Dhuhr.name_on(Weekday::Thu, Language::English),
+            "Dhuhr"
+        );
+        assert_eq!(
+            Prayer::Dhuhr.name_on(Weekday::Fri, Language::Arabic),
+            "الجمعة"
+        );
+        assert_eq!(
+            Prayer::Dhuhr.name_on(Weekday::Sat, Language::Arabic),
+            "الظهر"
+        );
+        assert_eq!(
+            Prayer::Dhuhr.name_on(Weekday::Fri, Language::French),
+            "Joumoua"
+        );
+    }
+
+    #[test]
+    fn prayer_categories() {
+        let categories = [
+            (Prayer::Imsak, PrayerCategory::Marker),
+            (Prayer::Fajr, PrayerCategory::Obligatory),
+            (Prayer::Sunrise, PrayerCategory::Marker),
+            (Prayer::Duha, PrayerCategory::Voluntary),
+            (Prayer::Dhuhr, PrayerCategory::Obligatory),
+            (Prayer::Asr, PrayerCategory::Obligatory),
+            (Prayer::Maghrib, PrayerCategory::Obligatory),
+            (Prayer::Isha, PrayerCategory::Obligatory),
+            (Prayer::MiddleOfTheNight, PrayerCategory::Voluntary),
+            (Prayer::Qiyam, PrayerCategory::Voluntary),
+            (Prayer::FajrTomorrow, PrayerCategory::Obligatory),
+        ];
+
+        for (prayer, category) in categories {
+            assert_eq!(prayer.category(), category);
+            assert_eq!(prayer.is_fard(), category == PrayerCategory::Obligatory);
+        }
+    }
+
+    #[test]
+    fn prayer_name_for_weekday() {
+        assert_eq!(Prayer::Dhuhr.name_for(Weekday::Fri), "Jumua");
+        assert_eq!(Prayer::Dhuhr.name_for(Weekday::Mon), "Dhuhr");
+        assert_eq!(Prayer::Asr.name_for(Weekday::Fri), "Asr");
+    }
+
+    #[test]
+    fn prayer_name_in_arabic() {
+        assert_eq!(Prayer::Fajr.name_localized(Language::Arabic), "الفجر");
+        assert_eq!(Prayer::Asr.name_localized(Language::Arabic), "العصر");
+        assert_eq!(Prayer::Maghrib.name_localized(Language::Arabic), "المغرب");
+        assert_eq!(Prayer::Isha.name_localized(Language::Arabic), "العشاء");
+    }
+
+    #[test]
+    fn prayer_name_in_french() {
+        assert_eq!(
+            Prayer::Sunrise.name_localized(Language::French),
+            "Lever du soleil"
+        );
+        assert_eq!(
+            Prayer::MiddleOfTheNight.name_localized(Language::French),
+            "Milieu de la nuit"
+        );
+        assert_eq!(
+            Prayer::FajrTomorrow.name_localized(Language::French),
+            "Fajr"
+        );
+    }
+
+    #[test]
+    fn prayer_from_name() {
+        assert_eq!(Prayer::from_name("fajr"), Some(Prayer::Fajr));
+        assert_eq!(Prayer::from_name("ISHA"), Some(Prayer::Isha));
+        assert_eq!(Prayer::from_name("Jumua"), Some(Prayer::Dhuhr));
+        assert_eq!(
+            Prayer::from_name("midnight"),
+            Some(Prayer::MiddleOfTheNight)
+        );
+        assert_eq!(
+            Prayer::from_name("middle-of-the-night"),
+            Some(Prayer::MiddleOfTheNight)
+        );
+        assert_eq!(Prayer::from_name("tahajjud"), None);
+        assert_eq!(Prayer::from_name(""), None);
+
+        // Displayed names round trip, FajrTomorrow is displayed as Fajr
+        for prayer in Prayer::value_variants() {
+            assert_eq!(
+                Prayer::from_name(&prayer.to_string()).map(|p| p.name()),
+                Some(prayer.name())
+            );
+        }
+    }
+
+    #[test]
+    fn display_prayer() {
+        assert_eq!(Prayer::Fajr.to_string(), "Fajr");
+        assert_eq!(format!("{}", Prayer::Qiyam), "Qiyam");
+    }
 }
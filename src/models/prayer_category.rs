@@ -0,0 +1,12 @@
+/// Kind of a prayer time, e.g. to style obligatory and voluntary prayers differently
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PrayerCategory {
+    /// The five daily prayers (fard)
+    Obligatory,
+    /// A time marking a limit rather than a prayer, e.g. sunrise
+    Marker,
+    /// A recommended prayer time, e.g. Duha or Qiyam
+    Voluntary,
+}
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 
-#[derive(PartialEq, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum PrayerTimeResolution {
     /// Indicates that no adjustment or correction was applied
     Normal,
@@ -22,6 +24,7 @@ impl Default for PrayerTimeResolution {
 }
 
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrayerTime {
     pub datetime: Option<DateTime<Utc>>,
     pub code: PrayerTimeResolution,
@@ -39,6 +42,21 @@ impl PrayerTime {
             message: String::default(),
         }
     }
+
+    /// Returns true if the prayer time is known and was not flagged as invalid
+    pub fn is_valid(&self) -> bool {
+        self.datetime.is_some() && self.code != PrayerTimeResolution::Invalid
+    }
+
+    /// Returns the prayer time, or the given default if it is unknown
+    pub fn datetime_or(&self, default: DateTime<Utc>) -> DateTime<Utc> {
+        self.datetime.unwrap_or(default)
+    }
+
+    /// Returns a reference to the prayer time, if known
+    pub fn as_datetime(&self) -> Option<&DateTime<Utc>> {
+        self.datetime.as_ref()
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -78,3 +96,31 @@ impl PrayerTimeBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn prayer_time_validity() {
+        let datetime = Utc.ymd(2022, 8, 1).and_hms(3, 57, 0);
+        let default = Utc.ymd(2022, 8, 1).and_hms(0, 0, 0);
+
+        let known = PrayerTime::new(Some(datetime));
+        assert!(known.is_valid());
+        assert_eq!(known.datetime_or(default), datetime);
+        assert_eq!(known.as_datetime(), Some(&datetime));
+
+        let unknown = PrayerTime::new(None);
+        assert!(!unknown.is_valid());
+        assert_eq!(unknown.datetime_or(default), default);
+        assert_eq!(unknown.as_datetime(), None);
+
+        let flagged = PrayerTimeBuilder::new(Some(datetime))
+            .code(PrayerTimeResolution::Invalid)
+            .build();
+        assert!(!flagged.is_valid());
+        assert_eq!(flagged.datetime_or(default), datetime);
+    }
+}
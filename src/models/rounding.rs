@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+
+use crate::astronomy::unit::Stride;
+
+/// Rounding applied to computed prayer times
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Rounding {
+    /// Round to the nearest minute
+    Nearest,
+    /// Round to the next minute
+    Up,
+    /// Truncate seconds
+    Down,
+    /// Keep seconds
+    None,
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        Rounding::Nearest
+    }
+}
+
+impl Rounding {
+    pub fn round(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Rounding::Nearest => time.nearest_minute(),
+            Rounding::Up => time.round_up_minute(),
+            Rounding::Down => time.round_down_minute(),
+            Rounding::None => time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn round_time_with_29_seconds() {
+        let time = Utc.ymd(2022, 8, 1).and_hms(4, 37, 29);
+
+        assert_eq!(
+            Rounding::Nearest.round(time),
+            Utc.ymd(2022, 8, 1).and_hms(4, 37, 0)
+        );
+        assert_eq!(
+            Rounding::Up.round(time),
+            Utc.ymd(2022, 8, 1).and_hms(4, 38, 0)
+        );
+        assert_eq!(
+            Rounding::Down.round(time),
+            Utc.ymd(2022, 8, 1).and_hms(4, 37, 0)
+        );
+        assert_eq!(Rounding::None.round(time), time);
+    }
+
+    #[test]
+    fn round_time_with_31_seconds() {
+        let time = Utc.ymd(2022, 8, 1).and_hms(4, 37, 31);
+
+        assert_eq!(
+            Rounding::Nearest.round(time),
+            Utc.ymd(2022, 8, 1).and_hms(4, 38, 0)
+        );
+        assert_eq!(
+            Rounding::Up.round(time),
+            Utc.ymd(2022, 8, 1).and_hms(4, 38, 0)
+        );
+        assert_eq!(
+            Rounding::Down.round(time),
+            Utc.ymd(2022, 8, 1).and_hms(4, 37, 0)
+        );
+        assert_eq!(Rounding::None.round(time), time);
+    }
+}
@@ -1,6 +1,8 @@
 use clap::ValueEnum;
 
-#[derive(PartialEq, Debug, Copy, Clone, ValueEnum)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 /// Enum to define how Isha prayer should be computed
 /// Imam Abu Hanifa consider that scripts refer to white twilight (shafaq), however
 /// other schools and two main students of Abu Hanifa (incl. Abu Yussuf)
@@ -10,8 +12,30 @@ pub enum Twilight {
     Red,
 }
 
+impl Twilight {
+    /// Returns the offset (in degrees) added to the Isha angle,
+    /// white twilight disappears about 3 degrees after the red one.
+    pub fn isha_angle_offset(&self) -> f64 {
+        match self {
+            Twilight::Red => 0.0,
+            Twilight::White => 3.0,
+        }
+    }
+}
+
 impl Default for Twilight {
     fn default() -> Self {
         Twilight::Red
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isha_angle_offset() {
+        assert_eq!(Twilight::Red.isha_angle_offset(), 0.0);
+        assert_eq!(Twilight::White.isha_angle_offset(), 3.0);
+    }
+}
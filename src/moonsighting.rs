@@ -0,0 +1,53 @@
+// Salati - Moonsighting Committee
+//
+//! Season adjusted twilights of the Moonsighting Committee,
+//! based on observational data instead of a fixed sun angle.
+use chrono::{DateTime, Utc};
+
+use crate::astronomy::ops;
+use crate::models::twilight::Twilight;
+
+/// Returns the Fajr time given the sunrise of the `day_of_year` (1 for January 1st)
+/// of the `year` at the `latitude`, following the Moonsighting Committee morning curve.
+/// The result is not rounded, prayer times round it along with the other times.
+///
+/// ```
+/// use salati::prelude::*;
+///
+/// // London, June 21st: the Moonsighting Committee timetable has Fajr at 02:43 BST
+/// let sunrise = Utc.ymd(2022, 6, 21).and_hms(3, 43, 0);
+/// let fajr = season_adjusted_morning_twilight(51.5074, 172, 2022, sunrise);
+///
+/// assert_eq!(fajr, Utc.ymd(2022, 6, 21).and_hms(1, 43, 16));
+/// ```
+pub fn season_adjusted_morning_twilight(
+    latitude: f64,
+    day_of_year: u32,
+    year: u32,
+    sunrise: DateTime<Utc>,
+) -> DateTime<Utc> {
+    ops::season_adjusted_morning_twilight(latitude, day_of_year, year, sunrise)
+}
+
+/// Returns the Isha time given the sunset of the `day_of_year` (1 for January 1st)
+/// of the `year` at the `latitude`, following the Moonsighting Committee evening curve
+/// of the given twilight. The result is not rounded.
+///
+/// ```
+/// use salati::prelude::*;
+///
+/// // London, December 21st: the Moonsighting Committee timetable has Isha at 17:32
+/// let sunset = Utc.ymd(2022, 12, 21).and_hms(15, 53, 0);
+/// let isha = season_adjusted_evening_twilight(51.5074, 355, 2022, sunset, Twilight::Red);
+///
+/// assert_eq!(isha, Utc.ymd(2022, 12, 21).and_hms(17, 31, 58));
+/// ```
+pub fn season_adjusted_evening_twilight(
+    latitude: f64,
+    day_of_year: u32,
+    year: u32,
+    sunset: DateTime<Utc>,
+    twilight: Twilight,
+) -> DateTime<Utc> {
+    ops::season_adjusted_evening_twilight(latitude, day_of_year, year, sunset, twilight)
+}
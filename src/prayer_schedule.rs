@@ -0,0 +1,245 @@
+use chrono::{Date, Duration, Utc};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::astronomy::solar::SolarTime;
+use crate::astronomy::unit::Coordinates;
+use crate::models::parameters::Parameters;
+use crate::models::prayer_time::PrayerTimeResolution;
+use crate::prayer_times::{calculate_solar_time, PrayerTimes};
+
+/// Lazily computes prayer times for consecutive days,
+/// only the solar times of the two next days are kept between iterations.
+pub(crate) struct DailyPrayerTimes {
+    date: Date<Utc>,
+    remaining_days: u32,
+    coordinates: Coordinates,
+    parameters: Parameters,
+    next_solar_times: Option<[(SolarTime, PrayerTimeResolution); 2]>,
+}
+
+impl DailyPrayerTimes {
+    pub(crate) fn new(
+        start: Date<Utc>,
+        days: u32,
+        coordinates: Coordinates,
+        parameters: Parameters,
+    ) -> DailyPrayerTimes {
+        DailyPrayerTimes {
+            date: start,
+            remaining_days: days,
+            coordinates,
+            parameters,
+            next_solar_times: None,
+        }
+    }
+
+    fn solar_time(&self, days: i64) -> (SolarTime, PrayerTimeResolution) {
+        let date = (self.date + Duration::days(days)).and_hms(0, 0, 0);
+
+        calculate_solar_time(date, self.coordinates, self.parameters)
+    }
+}
+
+impl Iterator for DailyPrayerTimes {
+    type Item = PrayerTimes;
+
+    fn next(&mut self) -> Option<PrayerTimes> {
+        if self.remaining_days == 0 {
+            return None;
+        }
+
+        let [today, tomorrow] = match self.next_solar_times {
+            Some(solar_times) => solar_times,
+            None => [self.solar_time(0), self.solar_time(1)],
+        };
+        let day_after_tomorrow = self.solar_time(2);
+        let prayer_times = PrayerTimes::with_solar_times(
+            self.date,
+            self.coordinates,
+            self.parameters,
+            [today, tomorrow, day_after_tomorrow],
+        );
+
+        self.next_solar_times = Some([tomorrow, day_after_tomorrow]);
+        self.date = self.date.succ();
+        self.remaining_days -= 1;
+
+        Some(prayer_times)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining_days = self.remaining_days as usize;
+
+        (remaining_days, Some(remaining_days))
+    }
+}
+
+impl ExactSizeIterator for DailyPrayerTimes {}
+
+/// Prayer times for a range of consecutive days, e.g. a monthly timetable
+#[derive(PartialEq, Debug, Clone)]
+pub struct PrayerSchedule {
+    pub prayer_times: Vec<PrayerTimes>,
+}
+
+impl PrayerSchedule {
+    /// Computes prayer times for `days` consecutive days starting from `start`
+    ///
+    /// The solar time of each day is computed once, and reused by the two previous dates.
+    pub fn new(
+        start: Date<Utc>,
+        days: u32,
+        coordinates: Coordinates,
+        parameters: Parameters,
+    ) -> PrayerSchedule {
+        PrayerSchedule {
+            prayer_times: DailyPrayerTimes::new(start, days, coordinates, parameters).collect(),
+        }
+    }
+
+    /// Computes prayer times for `days` consecutive days starting from `start`,
+    /// spreading the computation of each day over the rayon thread pool
+    #[cfg(feature = "rayon")]
+    pub fn par_new(
+        start: Date<Utc>,
+        days: u32,
+        coordinates: Coordinates,
+        parameters: Parameters,
+    ) -> PrayerSchedule {
+        let start_date = start.and_hms(0, 0, 0);
+        let solar_times: Vec<_> = (0..days as i64 + 2)
+            .into_par_iter()
+            .map(|day| {
+                calculate_solar_time(start_date + Duration::days(day), coordinates, parameters)
+            })
+            .collect();
+
+        let prayer_times = solar_times
+            .par_windows(3)
+            .enumerate()
+            .map(|(day, solar_times)| {
+                PrayerTimes::with_solar_times(
+                    start + Duration::days(day as i64),
+                    coordinates,
+                    parameters,
+                    [solar_times[0], solar_times[1], solar_times[2]],
+                )
+            })
+            .collect();
+
+        PrayerSchedule { prayer_times }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, PrayerTimes> {
+        self.prayer_times.iter()
+    }
+}
+
+impl IntoIterator for PrayerSchedule {
+    type Item = PrayerTimes;
+    type IntoIter = std::vec::IntoIter<PrayerTimes>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.prayer_times.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prayer_times::test_support::tunis_fixture;
+    use crate::prayer_times::SOLAR_TIME_COMPUTATIONS;
+    use chrono::TimeZone;
+
+    fn count_solar_time_computations<F: FnOnce()>(f: F) -> usize {
+        SOLAR_TIME_COMPUTATIONS.with(|count| count.set(0));
+        f();
+        SOLAR_TIME_COMPUTATIONS.with(|count| count.get())
+    }
+
+    #[test]
+    fn schedule_matches_daily_prayer_times() {
+        let (tunis, params) = tunis_fixture();
+        let schedule = PrayerSchedule::new(Utc.ymd(2022, 8, 1), 2, tunis, params);
+
+        assert_eq!(schedule.prayer_times.len(), 2);
+        assert_eq!(
+            schedule.prayer_times[0],
+            PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params)
+        );
+        assert_eq!(
+            schedule.prayer_times[1],
+            PrayerTimes::new(Utc.ymd(2022, 8, 2), tunis, params)
+        );
+    }
+
+    #[test]
+    fn schedule_reuses_solar_times_of_consecutive_days() {
+        let (tunis, params) = tunis_fixture();
+        let start = Utc.ymd(2022, 8, 1);
+
+        let daily = count_solar_time_computations(|| {
+            for day in 0..30 {
+                PrayerTimes::new(start + Duration::days(day), tunis, params);
+            }
+        });
+        let scheduled = count_solar_time_computations(|| {
+            PrayerSchedule::new(start, 30, tunis, params);
+        });
+
+        assert_eq!(daily, 90);
+        assert_eq!(scheduled, 32);
+    }
+
+    #[test]
+    fn year_iter_yields_every_day_of_the_year() {
+        let (tunis, params) = tunis_fixture();
+
+        assert_eq!(PrayerTimes::year_iter(2022, tunis, params).count(), 365);
+        assert_eq!(PrayerTimes::year_iter(2024, tunis, params).len(), 366);
+
+        let mut days = PrayerTimes::year_iter(2024, tunis, params).skip(59);
+        assert_eq!(
+            days.next(),
+            Some(PrayerTimes::new(Utc.ymd(2024, 2, 29), tunis, params))
+        );
+        assert_eq!(
+            days.next(),
+            Some(PrayerTimes::new(Utc.ymd(2024, 3, 1), tunis, params))
+        );
+        assert_eq!(
+            PrayerTimes::year_iter(2022, tunis, params).last(),
+            Some(PrayerTimes::new(Utc.ymd(2022, 12, 31), tunis, params))
+        );
+    }
+
+    #[test]
+    fn year_iter_reuses_solar_times_of_consecutive_days() {
+        let (tunis, params) = tunis_fixture();
+
+        let computations = count_solar_time_computations(|| {
+            let days: Vec<_> = PrayerTimes::year_iter(2022, tunis, params)
+                .take(30)
+                .collect();
+            assert_eq!(
+                days,
+                PrayerSchedule::new(Utc.ymd(2022, 1, 1), 30, tunis, params).prayer_times
+            );
+        });
+
+        // 32 for the iterator, and 32 for the schedule
+        assert_eq!(computations, 64);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_schedule_matches_sequential_schedule() {
+        let (tunis, params) = tunis_fixture();
+
+        assert_eq!(
+            PrayerSchedule::par_new(Utc.ymd(2022, 1, 1), 365, tunis, params),
+            PrayerSchedule::new(Utc.ymd(2022, 1, 1), 365, tunis, params)
+        );
+    }
+}
@@ -1,25 +1,48 @@
-use chrono::{Date, DateTime, Datelike, Duration, Utc};
+use std::iter;
+
+use chrono::{Date, DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use clap::ValueEnum;
 
 use crate::astronomy::ops;
 use crate::astronomy::solar::SolarTime;
 use crate::astronomy::unit::{Angle, Coordinates, Stride};
-use crate::constants::{is_high_latitude, HIGH_LATITUDE_RESOLUTION_MESSAGE};
+#[cfg(feature = "hijri")]
+use crate::constants::RAMADAN_ISHA_INTERVAL;
+use crate::constants::{
+    is_high_latitude, FAJR_AFTER_SUNRISE_MESSAGE, HIGH_LATITUDE_RESOLUTION_MESSAGE,
+    ISHA_BEFORE_MAGHRIB_MESSAGE, KAABA_COORDINATES, NEAREST_DAY_SEARCH_LIMIT,
+    NEAREST_TOWN_LATITUDE, POLAR_CIRCLE_RESOLUTION_MESSAGE, STANDARD_PRESSURE_HPA,
+    STANDARD_TEMPERATURE_C, UMM_AL_QURA_ISHA_INTERVAL, UNORDERED_PRAYER_TIME_MESSAGE,
+    UNRESOLVED_POLAR_CIRCLE_MESSAGE,
+};
+#[cfg(feature = "hijri")]
+use crate::hijri::{HijriDate, HijriMonth};
+use crate::models::day_type::DayType;
+use crate::models::language::Language;
+use crate::models::madhab::Madhab;
 use crate::models::method::Method;
 use crate::models::parameters::Parameters;
+use crate::models::polar_circle_resolution::PolarCircleResolution;
 use crate::models::prayer::Prayer;
 use crate::models::prayer_time::PrayerTime;
 use crate::models::prayer_time::PrayerTimeBuilder;
 use crate::models::prayer_time::PrayerTimeResolution;
+use crate::prayer_schedule::DailyPrayerTimes;
+use crate::prayer_times_local::PrayerTimesLocal;
 
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrayerTimes {
+    pub imsak: PrayerTime,
     pub fajr: PrayerTime,
     pub sunrise: PrayerTime,
     pub solar_sunrise: PrayerTime,
+    pub duha: PrayerTime,
     pub dhuhr: PrayerTime,
     pub asr: PrayerTime,
     pub maghrib: PrayerTime,
     pub solar_sunset: PrayerTime,
+    pub solar_sunrise_tomorrow: PrayerTime,
     pub isha: PrayerTime,
     pub middle_of_the_night: PrayerTime,
     pub qiyam: PrayerTime,
@@ -29,28 +52,273 @@ pub struct PrayerTimes {
     pub parameters: Parameters,
 }
 
-fn calculate_solar_time(
+#[cfg(test)]
+thread_local! {
+    /// Number of solar time computations on the current thread, used to profile tests
+    pub(crate) static SOLAR_TIME_COMPUTATIONS: std::cell::Cell<usize> =
+        const { std::cell::Cell::new(0) };
+}
+
+pub(crate) fn calculate_solar_time(
+    date: DateTime<Utc>,
+    coordinates: Coordinates,
+    parameters: Parameters,
+) -> (SolarTime, PrayerTimeResolution) {
+    #[cfg(test)]
+    SOLAR_TIME_COMPUTATIONS.with(|count| count.set(count.get() + 1));
+
+    let (solar_time, resolution) = resolve_solar_time(date, coordinates, parameters);
+
+    (apparent_or_mean(solar_time, parameters), resolution)
+}
+
+/// Returns the solar time following the true sun or the mean sun, see `use_equation_of_time`
+fn apparent_or_mean(solar_time: SolarTime, parameters: Parameters) -> SolarTime {
+    if parameters.use_equation_of_time {
+        solar_time
+    } else {
+        solar_time.to_mean_time()
+    }
+}
+
+fn resolve_solar_time(
     date: DateTime<Utc>,
     coordinates: Coordinates,
-    _parameters: Parameters,
+    parameters: Parameters,
 ) -> (SolarTime, PrayerTimeResolution) {
-    // todo: handle polar circle regions
-    (
-        SolarTime::new(date, coordinates),
-        PrayerTimeResolution::Normal,
-    )
+    let solar_time = SolarTime::with_atmosphere(
+        date,
+        coordinates,
+        parameters.pressure_hpa.unwrap_or(STANDARD_PRESSURE_HPA),
+        parameters.temperature_c.unwrap_or(STANDARD_TEMPERATURE_C),
+    );
+
+    if solar_time.sunrise.is_some() && solar_time.sunset.is_some() {
+        return (solar_time, PrayerTimeResolution::Normal);
+    }
+
+    // In polar circle regions, the sun does not set/rise in specific times of the year
+    let resolved_solar_time = match parameters.polar_circle_resolution {
+        PolarCircleResolution::NearestDay => nearest_day_solar_time(date, coordinates),
+        PolarCircleResolution::NearestTown => nearest_town_solar_time(date, coordinates),
+        PolarCircleResolution::UmmAlQura => umm_al_qura_solar_time(date, coordinates),
+        PolarCircleResolution::Unresolved => None,
+    };
+
+    match resolved_solar_time {
+        Some(resolved_solar_time) => (resolved_solar_time, PrayerTimeResolution::PolarCircle),
+        None => (solar_time, PrayerTimeResolution::Invalid),
+    }
+}
+
+/// Returns the solar time of the closest date (backward or forward) for which
+/// the sun rises and sets, moved to the given date.
+fn nearest_day_solar_time(date: DateTime<Utc>, coordinates: Coordinates) -> Option<SolarTime> {
+    for days in 1..=NEAREST_DAY_SEARCH_LIMIT {
+        for candidate in [date - Duration::days(days), date + Duration::days(days)] {
+            let solar_time = SolarTime::new(candidate, coordinates);
+
+            // Close to polar days, the sun may set after the next sunrise
+            let has_night = match (solar_time.sunrise, solar_time.sunset) {
+                (Some(sunrise), Some(sunset)) => sunset - sunrise < Duration::hours(24),
+                _ => false,
+            };
+
+            if has_night {
+                return Some(solar_time.with_date(date));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the solar time of the nearest habitable latitude (keeping the longitude)
+fn nearest_town_solar_time(date: DateTime<Utc>, coordinates: Coordinates) -> Option<SolarTime> {
+    let latitude = NEAREST_TOWN_LATITUDE.copysign(coordinates.latitude);
+    let solar_time = SolarTime::new(date, Coordinates::new(latitude, coordinates.longitude));
+
+    match (solar_time.sunrise, solar_time.sunset) {
+        (Some(_), Some(_)) => Some(solar_time),
+        _ => None,
+    }
+}
+
+/// Returns the solar time at Umm al-Qura (Makkah) latitude (keeping the longitude)
+fn umm_al_qura_solar_time(date: DateTime<Utc>, coordinates: Coordinates) -> Option<SolarTime> {
+    let latitude = KAABA_COORDINATES.latitude;
+    let solar_time = SolarTime::new(date, Coordinates::new(latitude, coordinates.longitude));
+
+    match (solar_time.sunrise, solar_time.sunset) {
+        (Some(_), Some(_)) => Some(solar_time),
+        _ => None,
+    }
+}
+
+/// Umm al-Qura polar circle resolution relies on fixed intervals rather than twilight angles
+fn uses_umm_al_qura_intervals(parameters: Parameters, resolution: PrayerTimeResolution) -> bool {
+    parameters.polar_circle_resolution == PolarCircleResolution::UmmAlQura
+        && resolution == PrayerTimeResolution::PolarCircle
+}
+
+/// Returns the Isha interval of the given date, Umm al-Qura and Qatar
+/// delay Isha during Ramadan when the `ramadan` parameter is set
+#[cfg_attr(not(feature = "hijri"), allow(unused_variables))]
+fn isha_interval(parameters: Parameters, prayer_date: DateTime<Utc>) -> i32 {
+    #[cfg(feature = "hijri")]
+    if parameters.ramadan
+        && matches!(parameters.method, Method::UmmAlQura | Method::Qatar)
+        && HijriDate::from_gregorian(prayer_date.date()).hijri_month() == HijriMonth::Ramadan
+    {
+        return RAMADAN_ISHA_INTERVAL;
+    }
+
+    parameters.isha_interval
+}
+
+/// Tags the given prayer time with the solar time resolution
+fn resolve_prayer_time(prayer_time: PrayerTime, resolution: PrayerTimeResolution) -> PrayerTime {
+    let message = match resolution {
+        PrayerTimeResolution::PolarCircle => POLAR_CIRCLE_RESOLUTION_MESSAGE,
+        PrayerTimeResolution::Invalid => UNRESOLVED_POLAR_CIRCLE_MESSAGE,
+        _ => return prayer_time,
+    };
+
+    PrayerTimeBuilder::new(prayer_time.datetime)
+        .code(resolution)
+        .message(String::from(message))
+        .build()
+}
+
+/// Flags the prayer time as invalid if it is not strictly before the given one,
+/// unknown prayer times are left as is
+fn ensure_before(prayer_time: PrayerTime, later: &PrayerTime, message: &str) -> PrayerTime {
+    match (prayer_time.datetime, later.datetime) {
+        (Some(datetime), Some(later)) if datetime >= later => {
+            PrayerTimeBuilder::new(Some(datetime))
+                .code(PrayerTimeResolution::Invalid)
+                .message(String::from(message))
+                .build()
+        }
+        _ => prayer_time,
+    }
+}
+
+/// Flags the prayer time as invalid if it is not strictly after the given one,
+/// unknown prayer times are left as is
+fn ensure_after(prayer_time: PrayerTime, earlier: &PrayerTime, message: &str) -> PrayerTime {
+    match (prayer_time.datetime, earlier.datetime) {
+        (Some(datetime), Some(earlier)) if datetime <= earlier => {
+            PrayerTimeBuilder::new(Some(datetime))
+                .code(PrayerTimeResolution::Invalid)
+                .message(String::from(message))
+                .build()
+        }
+        _ => prayer_time,
+    }
 }
 
 impl PrayerTimes {
     pub fn new(date: Date<Utc>, coordinates: Coordinates, parameters: Parameters) -> PrayerTimes {
         let prayer_date = date.and_hms(0, 0, 0);
-        let tomorrow = prayer_date.tomorrow();
-        let (solar_time, _today_prayer_time_resolution) =
-            calculate_solar_time(prayer_date, coordinates, parameters);
-        let (solar_time_tomorrow, _tomorrow_prayer_time_resolution) =
-            calculate_solar_time(tomorrow, coordinates, parameters);
+        let today = calculate_solar_time(prayer_date, coordinates, parameters);
+        let tomorrow = calculate_solar_time(prayer_date.tomorrow(), coordinates, parameters);
+        let day_after_tomorrow =
+            calculate_solar_time(prayer_date.tomorrow().tomorrow(), coordinates, parameters);
+
+        PrayerTimes::with_solar_times(
+            date,
+            coordinates,
+            parameters,
+            [today, tomorrow, day_after_tomorrow],
+        )
+    }
+
+    /// Computes prayer times of the given calendar date
+    pub fn from_naive(
+        date: NaiveDate,
+        coordinates: Coordinates,
+        parameters: Parameters,
+    ) -> PrayerTimes {
+        PrayerTimes::new(Utc.from_utc_date(&date), coordinates, parameters)
+    }
+
+    /// Computes prayer times of the (UTC) calendar date of the given instant
+    pub fn at(
+        datetime: DateTime<Utc>,
+        coordinates: Coordinates,
+        parameters: Parameters,
+    ) -> PrayerTimes {
+        PrayerTimes::new(datetime.date(), coordinates, parameters)
+    }
+
+    /// Computes prayer times from the given solar times of the date and the following day,
+    /// bypassing the solar computation (e.g. to use precomputed ephemerides).
+    /// The solar time of the day after tomorrow is extrapolated from the following day.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sun does not rise or set in the given solar times
+    pub fn from_solar_time(
+        date: Date<Utc>,
+        coordinates: Coordinates,
+        parameters: Parameters,
+        solar_time: SolarTime,
+        solar_time_tomorrow: SolarTime,
+    ) -> PrayerTimes {
+        let day_after_tomorrow =
+            solar_time_tomorrow.with_date(date.and_hms(0, 0, 0).tomorrow().tomorrow());
+
+        PrayerTimes::with_solar_times(
+            date,
+            coordinates,
+            parameters,
+            [
+                (solar_time, PrayerTimeResolution::Normal),
+                (solar_time_tomorrow, PrayerTimeResolution::Normal),
+                (day_after_tomorrow, PrayerTimeResolution::Normal),
+            ],
+        )
+    }
+
+    /// Returns an iterator over the prayer times of every day of the given year,
+    /// each day is computed when it's needed and reuses the solar times of the previous one
+    pub fn year_iter(
+        year: i32,
+        coordinates: Coordinates,
+        parameters: Parameters,
+    ) -> impl ExactSizeIterator<Item = PrayerTimes> {
+        let start = Utc.ymd(year, 1, 1);
+        let days = Utc
+            .ymd(year + 1, 1, 1)
+            .signed_duration_since(start)
+            .num_days();
+
+        DailyPrayerTimes::new(start, days as u32, coordinates, parameters)
+    }
+
+    /// Computes prayer times from the already resolved solar times of the given date
+    /// and the two following days
+    pub(crate) fn with_solar_times(
+        date: Date<Utc>,
+        coordinates: Coordinates,
+        parameters: Parameters,
+        solar_times: [(SolarTime, PrayerTimeResolution); 3],
+    ) -> PrayerTimes {
+        let prayer_date = date.and_hms(0, 0, 0);
+        let (solar_time, today_resolution) = solar_times[0];
+        let (solar_time_tomorrow, tomorrow_resolution) = solar_times[1];
+        let (solar_time_day_after_tomorrow, day_after_tomorrow_resolution) = solar_times[2];
+
+        // Without sunrise and sunset, e.g. for unresolved polar days and nights,
+        // the times based on them are unknown
+        if today_resolution == PrayerTimeResolution::Invalid
+            || tomorrow_resolution == PrayerTimeResolution::Invalid
+        {
+            return PrayerTimes::unresolved(prayer_date, coordinates, parameters);
+        }
 
-        let asr = solar_time.afternoon(parameters.madhab.shadow_length_ratio().into());
+        let asr = solar_time.afternoon(parameters.shadow_length_ratio());
         let night_duration = solar_time_tomorrow
             .sunrise
             .unwrap()
@@ -59,63 +327,173 @@ impl PrayerTimes {
         let final_fajr = PrayerTimes::calculate_fajr_time(
             parameters,
             solar_time,
+            today_resolution,
             night_duration,
             coordinates,
             prayer_date,
         );
+        let final_imsak = PrayerTimeBuilder::new(
+            final_fajr
+                .datetime
+                .map(|fajr| fajr.adjust_time(-parameters.imsak_interval as i64)),
+        )
+        .code(final_fajr.code)
+        .message(final_fajr.message.clone())
+        .build();
         let final_sunrise = solar_time
             .sunrise
             .unwrap()
             .adjust_time(parameters.time_adjustments(Prayer::Sunrise));
-        let final_dhuhr = solar_time
-            .transit
-            .unwrap()
-            .adjust_time(parameters.time_adjustments(Prayer::Dhuhr));
+        let final_duha = final_sunrise.adjust_time(parameters.duha_interval as i64);
+        let final_dhuhr =
+            solar_time.transit.unwrap() + Duration::seconds(parameters.dhuhr_offset_seconds());
         let final_asr = asr.adjust_time(parameters.time_adjustments(Prayer::Asr));
-        let final_maghrib = ops::adjust_time(
-            &solar_time.sunset.unwrap(),
-            parameters.time_adjustments(Prayer::Maghrib),
-        );
+        let maghrib = match parameters.maghrib_angle {
+            Some(angle) => solar_time.time_for_solar_angle(Angle::new(-angle), true),
+            None => solar_time.sunset.unwrap(),
+        };
+        let final_maghrib =
+            ops::adjust_time(&maghrib, parameters.time_adjustments(Prayer::Maghrib));
         let final_isha = PrayerTimes::calculate_isha_time(
             parameters,
             solar_time,
+            today_resolution,
             night_duration,
             coordinates,
             prayer_date,
         );
 
-        // Calculate the middle of the night and qiyam times
-        let (final_middle_of_night, final_qiyam, final_fajr_tomorrow) =
-            PrayerTimes::calculate_qiyam_time(
-                final_maghrib,
+        // Calculate the middle of the night and qiyam times,
+        // Jafari jurisprudence considers that the night starts at sunset
+        let night_start = if parameters.method == Method::Jafari {
+            solar_time.sunset.unwrap()
+        } else {
+            final_maghrib
+        };
+        let final_fajr_tomorrow = match solar_time_day_after_tomorrow.sunrise {
+            Some(sunrise_day_after_tomorrow) => PrayerTimes::calculate_fajr_time(
                 parameters,
                 solar_time_tomorrow,
+                tomorrow_resolution,
+                sunrise_day_after_tomorrow
+                    .signed_duration_since(solar_time_tomorrow.sunset.unwrap()),
                 coordinates,
-                tomorrow,
-            );
+                prayer_date.tomorrow(),
+            ),
+            None => resolve_prayer_time(PrayerTime::new(None), day_after_tomorrow_resolution),
+        };
+        let (final_middle_of_night, final_qiyam) = match (
+            parameters.solar_midnight,
+            solar_time.transit,
+            solar_time_tomorrow.transit,
+        ) {
+            // Unlike Maghrib and Fajr, the solar midnight is centered on the local night,
+            // whatever the distance between the location and the UTC day boundary
+            (true, Some(transit), Some(transit_tomorrow)) => {
+                let solar_midnight = transit + (transit_tomorrow - transit) / 2;
+                PrayerTimes::calculate_solar_midnight_qiyam_time(
+                    parameters,
+                    solar_midnight,
+                    &final_fajr_tomorrow,
+                )
+            }
+            _ => PrayerTimes::calculate_qiyam_time(parameters, night_start, &final_fajr_tomorrow),
+        };
+
+        // Round all prayer times the same way, once adjustments are applied
+        let round = |prayer_time: PrayerTime| PrayerTime {
+            datetime: prayer_time
+                .datetime
+                .map(|datetime| parameters.rounding.round(datetime)),
+            ..prayer_time
+        };
+        let resolve_today = |prayer_time| resolve_prayer_time(round(prayer_time), today_resolution);
+        let resolve_tomorrow =
+            |prayer_time| resolve_prayer_time(round(prayer_time), tomorrow_resolution);
+
+        let mut prayer_times = PrayerTimes {
+            imsak: resolve_today(final_imsak),
+            fajr: resolve_today(final_fajr),
+            sunrise: resolve_today(PrayerTime::new(Some(final_sunrise))),
+            solar_sunrise: resolve_today(PrayerTime::new(solar_time.sunrise)),
+            duha: resolve_today(PrayerTime::new(Some(final_duha))),
+            dhuhr: resolve_today(PrayerTime::new(Some(final_dhuhr))),
+            asr: resolve_today(PrayerTime::new(Some(final_asr))),
+            maghrib: resolve_today(PrayerTime::new(Some(final_maghrib))),
+            solar_sunset: resolve_today(PrayerTime::new(solar_time.sunset)),
+            solar_sunrise_tomorrow: resolve_tomorrow(PrayerTime::new(solar_time_tomorrow.sunrise)),
+            isha: resolve_today(final_isha),
+            middle_of_the_night: resolve_tomorrow(final_middle_of_night),
+            qiyam: resolve_tomorrow(final_qiyam),
+            fajr_tomorrow: resolve_tomorrow(final_fajr_tomorrow),
+            coordinates,
+            date: prayer_date,
+            parameters,
+        };
+        prayer_times.validate_ordering();
+
+        prayer_times
+    }
+
+    /// Returns the prayer times of a day whose sunrise or sunset could not be resolved,
+    /// all of them are unknown and flagged as invalid
+    fn unresolved(
+        prayer_date: DateTime<Utc>,
+        coordinates: Coordinates,
+        parameters: Parameters,
+    ) -> PrayerTimes {
+        let unresolved =
+            || resolve_prayer_time(PrayerTime::new(None), PrayerTimeResolution::Invalid);
 
         PrayerTimes {
-            fajr: final_fajr,
-            sunrise: PrayerTime::new(Some(final_sunrise)),
-            solar_sunrise: PrayerTime::new(solar_time.sunrise),
-            dhuhr: PrayerTime::new(Some(final_dhuhr)),
-            asr: PrayerTime::new(Some(final_asr)),
-            maghrib: PrayerTime::new(Some(final_maghrib)),
-            solar_sunset: PrayerTime::new(solar_time.sunset),
-            isha: final_isha,
-            middle_of_the_night: final_middle_of_night,
-            qiyam: final_qiyam,
-            fajr_tomorrow: final_fajr_tomorrow,
+            imsak: unresolved(),
+            fajr: unresolved(),
+            sunrise: unresolved(),
+            solar_sunrise: unresolved(),
+            duha: unresolved(),
+            dhuhr: unresolved(),
+            asr: unresolved(),
+            maghrib: unresolved(),
+            solar_sunset: unresolved(),
+            solar_sunrise_tomorrow: unresolved(),
+            isha: unresolved(),
+            middle_of_the_night: unresolved(),
+            qiyam: unresolved(),
+            fajr_tomorrow: unresolved(),
             coordinates,
             date: prayer_date,
             parameters,
         }
     }
 
+    /// Flags the prayer times breaking the chronological order of the day
+    /// (Fajr < Sunrise < Dhuhr < Asr < Maghrib < Isha), as it may happen at extreme latitudes
+    fn validate_ordering(&mut self) {
+        self.fajr = ensure_before(self.fajr.clone(), &self.sunrise, FAJR_AFTER_SUNRISE_MESSAGE);
+        self.dhuhr = ensure_after(
+            self.dhuhr.clone(),
+            &self.sunrise,
+            UNORDERED_PRAYER_TIME_MESSAGE,
+        );
+        self.asr = ensure_after(self.asr.clone(), &self.dhuhr, UNORDERED_PRAYER_TIME_MESSAGE);
+        self.maghrib = ensure_after(
+            self.maghrib.clone(),
+            &self.asr,
+            UNORDERED_PRAYER_TIME_MESSAGE,
+        );
+        self.isha = ensure_after(
+            self.isha.clone(),
+            &self.maghrib,
+            ISHA_BEFORE_MAGHRIB_MESSAGE,
+        );
+    }
+
     pub fn prayer_time(&self, prayer: Prayer) -> PrayerTime {
         match prayer {
+            Prayer::Imsak => self.imsak.clone(),
             Prayer::Fajr => self.fajr.clone(),
             Prayer::Sunrise => self.sunrise.clone(),
+            Prayer::Duha => self.duha.clone(),
             Prayer::Dhuhr => self.dhuhr.clone(),
             Prayer::Asr => self.asr.clone(),
             Prayer::Maghrib => self.maghrib.clone(),
@@ -126,209 +504,430 @@ impl PrayerTimes {
         }
     }
 
+    /// Returns the hijri date of the prayer times day
+    #[cfg(feature = "hijri")]
+    pub fn hijri_date(&self) -> HijriDate {
+        HijriDate::from_gregorian(self.date.date())
+    }
+
+    /// Returns the name of the given prayer on this day, Dhuhr is named Jumua on Fridays
+    pub fn name(&self, prayer: Prayer) -> String {
+        self.name_localized(prayer, Language::English)
+    }
+
+    /// Returns the name of the given prayer on this day in the given language
+    pub fn name_localized(&self, prayer: Prayer, lang: Language) -> String {
+        prayer.name_on(self.date.weekday(), lang)
+    }
+
+    /// Returns true if the prayer times are computed for a Friday, Dhuhr being then the Jumua prayer
+    pub fn is_jumua(&self) -> bool {
+        self.date.weekday() == Weekday::Fri
+    }
+
+    /// Returns an iterator over the day prayers in chronological order
+    pub fn iter(&self) -> impl Iterator<Item = (Prayer, PrayerTime)> + '_ {
+        Prayer::all()
+            .into_iter()
+            .map(|prayer| (prayer, self.prayer_time(prayer)))
+    }
+
+    /// Returns the prayers (including Imsak, Duha and tomorrow's Fajr) whose time shifted
+    /// in the other prayer times, along with the signed shift (positive when the prayer
+    /// is later in the other ones).
+    ///
+    /// Times are compared relatively to their day, so consecutive days can be compared,
+    /// prayers that are unknown in either prayer times are skipped.
+    pub fn diff(&self, other: &PrayerTimes) -> Vec<(Prayer, Duration)> {
+        let days = other.date.signed_duration_since(self.date);
+
+        Prayer::value_variants()
+            .iter()
+            .filter_map(|&prayer| {
+                let datetime = self.prayer_time(prayer).datetime?;
+                let other_datetime = other.prayer_time(prayer).datetime?;
+
+                Some((prayer, other_datetime - days - datetime))
+            })
+            .filter(|(_, shift)| *shift != Duration::zero())
+            .collect()
+    }
+
+    /// Returns the next `n` prayers starting after the given time, in chronological order
+    ///
+    /// When today's remaining prayers are not enough, the prayer times of the following days
+    /// are computed, their prayers are returned with their usual names (e.g. `Fajr`).
+    pub fn upcoming(&self, from: DateTime<Utc>, n: usize) -> Vec<(Prayer, DateTime<Utc>)> {
+        let mut upcoming = Vec::with_capacity(n);
+        let mut prayer_times = self.clone();
+
+        // Each day has at least one prayer, unless prayer times cannot be resolved
+        for _ in 0..=n {
+            let remaining = prayer_times
+                .iter()
+                .filter_map(|(prayer, prayer_time)| Some((prayer, prayer_time.datetime?)))
+                .filter(|(_, datetime)| *datetime > from);
+            upcoming.extend(remaining.take(n - upcoming.len()));
+
+            if upcoming.len() == n {
+                break;
+            }
+            prayer_times = PrayerTimes::new(
+                prayer_times.date.date().succ(),
+                self.coordinates,
+                self.parameters,
+            );
+        }
+
+        upcoming
+    }
+
+    /// Returns whether the sun rises and sets at this date and location
+    pub fn day_type(&self) -> DayType {
+        let solar_time = SolarTime::new(self.date, self.coordinates);
+
+        if solar_time.is_polar_day() {
+            DayType::MidnightSun
+        } else if solar_time.is_polar_night() {
+            DayType::PolarNight
+        } else {
+            DayType::Normal
+        }
+    }
+
+    /// Returns the duration from sunrise to sunset, `None` during polar day or night
+    pub fn day_length(&self) -> Option<Duration> {
+        self.duration_between(&self.solar_sunrise, &self.solar_sunset)
+    }
+
+    /// Returns the duration of the night from sunset to the next sunrise, which is divided
+    /// by the high latitude rules. In polar circle regions, it's the night of the resolved
+    /// sunset and sunrise, see `PolarCircleResolution`
+    pub fn night_duration(&self) -> Option<Duration> {
+        Some(self.solar_sunrise_tomorrow.datetime? - self.solar_sunset.datetime?)
+    }
+
+    /// Returns the duration of the fast, from Fajr to Maghrib,
+    /// `None` during polar day or night
+    pub fn fasting_duration(&self) -> Option<Duration> {
+        self.duration_between(&self.fajr, &self.maghrib)
+    }
+
+    fn duration_between(&self, start: &PrayerTime, end: &PrayerTime) -> Option<Duration> {
+        if self.day_type() != DayType::Normal {
+            return None;
+        }
+
+        Some(end.datetime? - start.datetime?)
+    }
+
+    /// Returns the prayer times with each time rounded to the nearest multiple of the given
+    /// minutes since midnight, e.g. for timetables showing times in 5 minutes steps,
+    /// or `None` when `minutes` is not positive or a rounded time is out of range.
+    pub fn rounded_to(&self, minutes: i64) -> Option<PrayerTimes> {
+        if minutes <= 0 {
+            return None;
+        }
+
+        let round = |prayer_time: &PrayerTime| -> Option<PrayerTime> {
+            let datetime = match prayer_time.datetime {
+                Some(datetime) => Some(datetime.try_nearest_minutes(minutes)?),
+                None => None,
+            };
+
+            Some(PrayerTime {
+                datetime,
+                ..prayer_time.clone()
+            })
+        };
+
+        Some(PrayerTimes {
+            imsak: round(&self.imsak)?,
+            fajr: round(&self.fajr)?,
+            sunrise: round(&self.sunrise)?,
+            solar_sunrise: round(&self.solar_sunrise)?,
+            duha: round(&self.duha)?,
+            dhuhr: round(&self.dhuhr)?,
+            asr: round(&self.asr)?,
+            maghrib: round(&self.maghrib)?,
+            solar_sunset: round(&self.solar_sunset)?,
+            solar_sunrise_tomorrow: round(&self.solar_sunrise_tomorrow)?,
+            isha: round(&self.isha)?,
+            middle_of_the_night: round(&self.middle_of_the_night)?,
+            qiyam: round(&self.qiyam)?,
+            fajr_tomorrow: round(&self.fajr_tomorrow)?,
+            ..self.clone()
+        })
+    }
+
+    /// Returns the prayer times converted to the given timezone
+    pub fn in_timezone<Tz: TimeZone>(&self, timezone: Tz) -> PrayerTimesLocal<Tz> {
+        PrayerTimesLocal::new(self, timezone)
+    }
+
+    /// Returns the Asr time following the given madhab, with the Asr adjustments and rounding
+    /// of the parameters, so that both Asr times can be shown from the same prayer times
+    pub fn asr_for(&self, madhab: Madhab) -> DateTime<Utc> {
+        let (solar_time, _) = calculate_solar_time(self.date, self.coordinates, self.parameters);
+        let asr = solar_time.afternoon(madhab.shadow_length_ratio().into());
+
+        self.parameters
+            .rounding
+            .round(asr.adjust_time(self.parameters.time_adjustments(Prayer::Asr)))
+    }
+
+    /// Returns the solar midnight following this day, i.e. when the sun is at its lowest
+    pub fn solar_midnight(&self) -> DateTime<Utc> {
+        apparent_or_mean(SolarTime::new(self.date, self.coordinates), self.parameters)
+            .solar_midnight()
+    }
+
+    /// Returns the makruh windows, during which voluntary prayers are disliked:
+    /// at sunrise, when the sun is at its zenith, and at sunset.
+    ///
+    /// The durations of the windows are set by `parameters.forbidden_durations`,
+    /// by default 15 minutes after sunrise, 5 minutes before the solar transit,
+    /// and 15 minutes before sunset.
+    pub fn forbidden_windows(&self) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let durations = self.parameters.forbidden_durations;
+        let transit =
+            apparent_or_mean(SolarTime::new(self.date, self.coordinates), self.parameters).transit;
+
+        [
+            self.solar_sunrise
+                .datetime
+                .map(|sunrise| (sunrise, sunrise.adjust_time(durations.sunrise))),
+            transit.map(|transit| (transit.adjust_time(-durations.zenith), transit)),
+            self.solar_sunset
+                .datetime
+                .map(|sunset| (sunset.adjust_time(-durations.sunset), sunset)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Returns the iqamah time of the given prayer,
+    /// `None` for prayers without iqamah (e.g. Sunrise, Qiyam)
+    pub fn iqamah_time(&self, prayer: Prayer) -> Option<DateTime<Utc>> {
+        let offset = self.parameters.iqamah_offset(prayer)?;
+        let adhan = self.prayer_time(prayer).datetime?;
+
+        Some(adhan.adjust_time(offset))
+    }
+
     pub fn time(&self, prayer: Prayer) -> DateTime<Utc> {
         let prayer_time = self.prayer_time(prayer);
         prayer_time.datetime.unwrap()
     }
 
-    /// Returns current prayer if any
+    /// Returns the prayer at the given time if any
     ///
     /// Indeed, this method returns the last started prayer time, with the following assumptions:
     /// 1) next day did not start yet
     /// 2) prayer time last to the next prayer
-    fn current_prayer(&self, time: DateTime<Utc>) -> Option<Prayer> {
-        let current_prayer: Option<Prayer>;
-
-        if self
-            .fajr_tomorrow
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::FajrTomorrow)
-        } else if self
-            .qiyam
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::Qiyam)
-        } else if self
-            .middle_of_the_night
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::MiddleOfTheNight)
-        } else if self
-            .isha
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::Isha);
-        } else if self
-            .maghrib
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::Maghrib);
-        } else if self.asr.datetime?.signed_duration_since(time).num_seconds() <= 0 {
-            current_prayer = Some(Prayer::Asr);
-        } else if self
-            .dhuhr
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::Dhuhr);
-        } else if self
-            .sunrise
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::Sunrise);
-        } else if self
-            .fajr
-            .datetime?
-            .signed_duration_since(time)
-            .num_seconds()
-            <= 0
-        {
-            current_prayer = Some(Prayer::Fajr);
-        } else {
-            current_prayer = None;
+    ///
+    /// Unknown prayer times (e.g. Fajr at high latitudes) are skipped.
+    pub fn current_at(&self, time: DateTime<Utc>) -> Option<Prayer> {
+        let prayers = iter::once(Prayer::FajrTomorrow).chain(Prayer::all().into_iter().rev());
+
+        for prayer in prayers {
+            if let Some(prayer_time) = self.prayer_time(prayer).datetime {
+                if prayer_time.signed_duration_since(time).num_seconds() <= 0 {
+                    return Some(prayer);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns true if the given time falls within the time of the given prayer,
+    /// i.e. from its start (inclusive) until the start of the next prayer (exclusive)
+    ///
+    /// Tomorrow's Fajr lasts until tomorrow's sunrise, which is out of these prayer times,
+    /// so it's considered valid from its start onwards.
+    pub fn is_time_for(&self, prayer: Prayer, at: DateTime<Utc>) -> bool {
+        let start = match self.prayer_time(prayer).datetime {
+            Some(start) => start,
+            None => return false,
+        };
+        if at < start {
+            return false;
+        }
+
+        match prayer {
+            Prayer::FajrTomorrow => true,
+            _ => self
+                .prayer_time(prayer.next())
+                .datetime
+                .map_or(false, |end| at < end),
         }
+    }
 
-        current_prayer
+    /// Returns current prayer, `None` before Fajr (e.g. when computing a future date)
+    pub fn current(&self) -> Option<Prayer> {
+        self.current_at(Utc::now())
     }
 
-    /// Returns current prayer
-    pub fn current(&self) -> Prayer {
-        self.current_prayer(Utc::now()).expect("Out of bounds")
+    /// Returns next prayer, `None` when there is no current prayer
+    /// or once tomorrow's Fajr started
+    pub fn next(&self) -> Option<Prayer> {
+        self.next_at(Utc::now())
     }
 
-    /// Returns next prayer
-    pub fn next(&self) -> Prayer {
-        match self.current() {
-            Prayer::Fajr => Prayer::Sunrise,
-            Prayer::Sunrise => Prayer::Dhuhr,
-            Prayer::Dhuhr => Prayer::Asr,
-            Prayer::Asr => Prayer::Maghrib,
-            Prayer::Maghrib => Prayer::Isha,
-            Prayer::Isha => Prayer::MiddleOfTheNight,
-            Prayer::MiddleOfTheNight => Prayer::Qiyam,
-            Prayer::Qiyam => Prayer::FajrTomorrow,
-            _ => Prayer::FajrTomorrow,
+    /// Returns the prayer following the one at the given time, if any
+    ///
+    /// Once tomorrow's Fajr started, the next prayer is the sunrise of the next day
+    /// which is out of these prayer times, so `None` is returned.
+    pub fn next_at(&self, time: DateTime<Utc>) -> Option<Prayer> {
+        match self.current_at(time)? {
+            Prayer::FajrTomorrow => None,
+            prayer => Some(prayer.next()),
         }
     }
 
     /// Returns time remaining to next prayer
     pub fn time_remaining(&self) -> (u32, u32) {
-        let next_time = self.time(self.next());
-        let now = Utc::now();
-        let now_to_next = next_time.signed_duration_since(now).num_seconds() as f64;
-        let whole: f64 = now_to_next / 60.0 / 60.0;
-        let fract = whole.fract();
-        let hours = whole.trunc() as u32;
-        let minutes = (fract * 60.0).round() as u32;
+        self.time_remaining_at(Utc::now())
+    }
+
+    /// Returns time remaining (hours, minutes) from the given time to the next prayer,
+    /// see [duration_until_next](#method.duration_until_next)
+    pub fn time_remaining_at(&self, time: DateTime<Utc>) -> (u32, u32) {
+        // Round to the nearest minute
+        let minutes = (self.duration_until_next(time).num_seconds() + 30) / 60;
 
-        (hours, minutes)
+        ((minutes / 60) as u32, (minutes % 60) as u32)
+    }
+
+    /// Returns the duration from the given time to the next prayer
+    ///
+    /// Unknown prayer times (e.g. Isha at high latitudes) are skipped up to tomorrow's Fajr,
+    /// and once tomorrow's Fajr is passed too, a zero duration is returned: callers should
+    /// compute the following day's prayer times to handle the day rollover.
+    pub fn duration_until_next(&self, from: DateTime<Utc>) -> Duration {
+        // Before Fajr, the next prayer is Fajr
+        Prayer::all()
+            .into_iter()
+            .chain(iter::once(Prayer::FajrTomorrow))
+            .filter_map(|prayer| self.prayer_time(prayer).datetime)
+            .find(|time| *time > from)
+            .map_or(Duration::zero(), |time| time.signed_duration_since(from))
     }
 
     fn calculate_fajr_time(
         parameters: Parameters,
         solar_time: SolarTime,
+        resolution: PrayerTimeResolution,
         night: Duration,
         coordinates: Coordinates,
         prayer_date: DateTime<Utc>,
     ) -> PrayerTime {
-        let mut fajr = solar_time.time_for_solar_angle(Angle::new(-parameters.fajr_angle), false);
+        let mut fajr: Option<DateTime<Utc>>;
         let mut message = "";
         let mut prayer_time_resolution = PrayerTimeResolution::default();
 
-        // This is a special case for Moonsighting Committee: latitude above 55.0
-        if parameters.method == Method::MoonsightingCommittee
-            && is_high_latitude(coordinates, Some(parameters.method))
-        {
-            let night_fraction = night.num_seconds() / 7;
+        if parameters.fajr_interval > 0 {
             fajr = solar_time
                 .sunrise
                 .unwrap()
-                .checked_add_signed(Duration::seconds(-night_fraction as i64))
-                .unwrap();
-        }
-
-        // At latitudes:
-        // 1) between 55 and 60: Fajr and Isha time are very close to each other
-        // 2) between 60 and 65: hardship prevails (Fajr and Isha time becomes more closer)
-        // 3) above 65: sun does not set/rise for a number of days.
-        //
-        // For the 3rd case, we will fall to polar circle resolution method
-        //    the given solar_time will not be the original one based on CirclePolarResolution strategy
-        // Otherwise, we will return the latest time for fajr
-        let safe_fajr = if parameters.method == Method::MoonsightingCommittee {
-            let day_of_year = prayer_date.ordinal();
-            ops::season_adjusted_morning_twilight(
-                coordinates.latitude,
-                day_of_year,
-                prayer_date.year() as u32,
-                solar_time.sunrise.unwrap(),
-            )
+                .checked_add_signed(Duration::minutes(-parameters.fajr_interval as i64));
         } else {
-            let portion = parameters.night_portions().0;
-            let night_fraction = portion * (night.num_seconds() as f64);
+            // At high latitudes, the sun may not reach the fajr angle
+            fajr =
+                solar_time.checked_time_for_solar_angle(Angle::new(-parameters.fajr_angle), false);
 
-            solar_time
+            // This is a special case for Moonsighting Committee: latitude above 55.0
+            if parameters.method == Method::MoonsightingCommittee
+                && is_high_latitude(coordinates, Some(parameters.method))
+            {
+                let night_fraction = night.num_seconds() / 7;
+                fajr = solar_time
+                    .sunrise
+                    .unwrap()
+                    .checked_add_signed(Duration::seconds(-night_fraction));
+            }
+
+            // At latitudes:
+            // 1) between 55 and 60: Fajr and Isha time are very close to each other
+            // 2) between 60 and 65: hardship prevails (Fajr and Isha time becomes more closer)
+            // 3) above 65: sun does not set/rise for a number of days.
+            //
+            // For the 3rd case, we will fall to polar circle resolution method
+            //    the given solar_time will not be the original one based on CirclePolarResolution strategy
+            // Otherwise, we will return the latest time for fajr
+            let safe_fajr = if parameters.method == Method::MoonsightingCommittee {
+                let day_of_year = prayer_date.ordinal();
+                ops::season_adjusted_morning_twilight(
+                    coordinates.latitude,
+                    day_of_year,
+                    prayer_date.year() as u32,
+                    solar_time.sunrise.unwrap(),
+                )
+            } else {
+                let portion = parameters.night_portions(coordinates).0;
+                let night_fraction = portion * (night.num_seconds() as f64);
+
+                solar_time
+                    .sunrise
+                    .unwrap()
+                    .checked_add_signed(Duration::seconds(-night_fraction as i64))
+                    .unwrap()
+            };
+
+            // This check is applied only at high latitudes, unless set otherwise by `fajr_floor`
+            if parameters.has_fajr_floor(coordinates) && fajr.map_or(true, |fajr| fajr < safe_fajr)
+            {
+                fajr = Some(safe_fajr);
+                prayer_time_resolution = PrayerTimeResolution::HighLatitudeRule;
+                message = HIGH_LATITUDE_RESOLUTION_MESSAGE;
+            }
+        }
+
+        // In polar circle regions, Umm al-Qura resolution sets Fajr at the last seventh of the night
+        if uses_umm_al_qura_intervals(parameters, resolution) {
+            let night_fraction = night.num_seconds() / 7;
+            fajr = solar_time
                 .sunrise
                 .unwrap()
-                .checked_add_signed(Duration::seconds(-night_fraction as i64))
-                .unwrap()
-        };
-
-        // This check is applied only at high latitudes
-        if is_high_latitude(coordinates, None) && fajr < safe_fajr {
-            fajr = safe_fajr;
-            prayer_time_resolution = PrayerTimeResolution::HighLatitudeRule;
-            message = HIGH_LATITUDE_RESOLUTION_MESSAGE;
+                .checked_add_signed(Duration::seconds(-night_fraction));
         }
 
         // finally, let's apply time adjustments
-        fajr = fajr.adjust_time(parameters.time_adjustments(Prayer::Fajr));
-        PrayerTimeBuilder::new(Some(fajr))
+        match fajr {
+            Some(fajr) => PrayerTimeBuilder::new(Some(
+                fajr.adjust_time(parameters.time_adjustments(Prayer::Fajr)),
+            ))
             .code(prayer_time_resolution)
             .message(String::from(message))
-            .build()
+            .build(),
+            None => PrayerTime::new(None),
+        }
     }
 
     fn calculate_isha_time(
         parameters: Parameters,
         solar_time: SolarTime,
+        resolution: PrayerTimeResolution,
         night: Duration,
         coordinates: Coordinates,
         prayer_date: DateTime<Utc>,
     ) -> PrayerTime {
-        let mut isha: DateTime<Utc>;
+        let mut isha: Option<DateTime<Utc>>;
         let mut message = "";
         let mut prayer_time_resolution = PrayerTimeResolution::default();
 
-        if parameters.isha_interval > 0 {
+        let isha_interval = isha_interval(parameters, prayer_date);
+        if isha_interval > 0 {
             isha = solar_time
                 .sunset
                 .unwrap()
-                .checked_add_signed(Duration::seconds((parameters.isha_interval * 60) as i64))
-                .unwrap();
+                .checked_add_signed(Duration::seconds((isha_interval * 60) as i64));
         } else {
-            isha = solar_time.time_for_solar_angle(Angle::new(-parameters.isha_angle), true);
+            let isha_angle = parameters.isha_angle + parameters.twilight.isha_angle_offset();
+            // At high latitudes, the sun may not reach the isha angle
+            isha = solar_time.checked_time_for_solar_angle(Angle::new(-isha_angle), true);
 
             // This is a special case for Moonsighting Committee: latitude above 55.0
             if parameters.method == Method::MoonsightingCommittee
@@ -338,8 +937,7 @@ impl PrayerTimes {
                 isha = solar_time
                     .sunset
                     .unwrap()
-                    .checked_add_signed(Duration::seconds(night_fraction))
-                    .unwrap();
+                    .checked_add_signed(Duration::seconds(night_fraction));
             }
 
             let safe_isha = if parameters.method == Method::MoonsightingCommittee {
@@ -350,9 +948,10 @@ impl PrayerTimes {
                     day_of_year,
                     prayer_date.year() as u32,
                     solar_time.sunset.unwrap(),
+                    parameters.twilight,
                 )
             } else {
-                let portion = parameters.night_portions().1;
+                let portion = parameters.night_portions(coordinates).1;
                 let night_fraction = portion * (night.num_seconds() as f64);
 
                 solar_time
@@ -363,65 +962,1254 @@ impl PrayerTimes {
             };
 
             // This check is applied only at high latitudes
-            if is_high_latitude(coordinates, None) && isha > safe_isha {
-                isha = safe_isha;
+            if parameters.is_high_latitude(coordinates)
+                && isha.map_or(true, |isha| isha > safe_isha)
+            {
+                isha = Some(safe_isha);
                 prayer_time_resolution = PrayerTimeResolution::HighLatitudeRule;
                 message = HIGH_LATITUDE_RESOLUTION_MESSAGE;
             }
         }
 
+        // In polar circle regions, Umm al-Qura resolution sets Isha at a fixed interval after sunset
+        if uses_umm_al_qura_intervals(parameters, resolution) {
+            isha = solar_time
+                .sunset
+                .unwrap()
+                .checked_add_signed(Duration::minutes(UMM_AL_QURA_ISHA_INTERVAL));
+        }
+
         // finally, let's apply time adjustments
-        isha = isha.adjust_time(parameters.time_adjustments(Prayer::Isha));
-        PrayerTimeBuilder::new(Some(isha))
+        match isha {
+            Some(isha) => PrayerTimeBuilder::new(Some(
+                isha.adjust_time(parameters.time_adjustments(Prayer::Isha)),
+            ))
             .code(prayer_time_resolution)
             .message(String::from(message))
-            .build()
+            .build(),
+            None => PrayerTime::new(None),
+        }
     }
 
     fn calculate_qiyam_time(
-        current_maghrib: DateTime<Utc>,
         parameters: Parameters,
-        solar_time: SolarTime,
-        coordinates: Coordinates,
-        prayer_date: DateTime<Utc>,
-    ) -> (PrayerTime, PrayerTime, PrayerTime) {
-        // this is actually day after tomorrow
-        let tomorrow = prayer_date.tomorrow();
-        let (solar_time_tomorrow, _tomorrow_prayer_time_resolution) =
-            calculate_solar_time(tomorrow, coordinates, parameters);
-
-        let night = solar_time_tomorrow
-            .sunrise
-            .unwrap()
-            .signed_duration_since(solar_time.sunset.unwrap());
-
-        let tomorrow_fajr = PrayerTimes::calculate_fajr_time(
-            parameters,
-            solar_time,
-            night,
-            coordinates,
-            prayer_date,
-        );
+        night_start: DateTime<Utc>,
+        tomorrow_fajr: &PrayerTime,
+    ) -> (PrayerTime, PrayerTime) {
+        // Without tomorrow's Fajr, e.g. when the sun does not reach the Fajr angle,
+        // the end of the night is unknown
+        let tomorrow_fajr = match tomorrow_fajr.datetime {
+            Some(tomorrow_fajr) => tomorrow_fajr,
+            None => return (PrayerTime::new(None), PrayerTime::new(None)),
+        };
         let night_duration = tomorrow_fajr
-            .datetime
-            .unwrap()
-            .signed_duration_since(current_maghrib)
+            .signed_duration_since(night_start)
             .num_seconds() as f64;
         let middle_night_portion = (night_duration / 2.0) as i64;
         let last_third_portion = (night_duration * (2.0 / 3.0)) as i64;
-        let middle_of_night = current_maghrib
+        let middle_of_night = night_start
             .checked_add_signed(Duration::seconds(middle_night_portion))
             .unwrap()
-            .nearest_minute();
-        let last_third_of_night = current_maghrib
+            .adjust_time(parameters.time_adjustments(Prayer::MiddleOfTheNight));
+        let last_third_of_night = night_start
             .checked_add_signed(Duration::seconds(last_third_portion))
             .unwrap()
-            .nearest_minute();
+            .adjust_time(parameters.time_adjustments(Prayer::Qiyam));
+
+        (
+            PrayerTimeBuilder::new(Some(middle_of_night)).build(),
+            PrayerTimeBuilder::new(Some(last_third_of_night)).build(),
+        )
+    }
+
+    /// The night is considered symmetric around the solar midnight,
+    /// its last third starts a third of the way from the solar midnight to Fajr
+    fn calculate_solar_midnight_qiyam_time(
+        parameters: Parameters,
+        solar_midnight: DateTime<Utc>,
+        tomorrow_fajr: &PrayerTime,
+    ) -> (PrayerTime, PrayerTime) {
+        let tomorrow_fajr = match tomorrow_fajr.datetime {
+            Some(tomorrow_fajr) => tomorrow_fajr,
+            None => return (PrayerTime::new(None), PrayerTime::new(None)),
+        };
+        let half_night = tomorrow_fajr.signed_duration_since(solar_midnight);
+        let middle_of_night =
+            solar_midnight.adjust_time(parameters.time_adjustments(Prayer::MiddleOfTheNight));
+        let last_third_of_night = (solar_midnight + half_night / 3)
+            .adjust_time(parameters.time_adjustments(Prayer::Qiyam));
 
         (
             PrayerTimeBuilder::new(Some(middle_of_night)).build(),
             PrayerTimeBuilder::new(Some(last_third_of_night)).build(),
-            tomorrow_fajr,
         )
     }
 }
+
+/// Fixtures shared by the tests of the prayer times modules
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use crate::models::parameters::ParametersBuilder;
+
+    /// Location and parameters of the Tunis prayer times
+    pub(crate) fn tunis_fixture() -> (Coordinates, Parameters) {
+        (
+            Coordinates::new(36.8065, 10.1815),
+            ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi),
+        )
+    }
+
+    /// Prayer times of Tunis on August 1st 2022, following the Muslim World League method
+    pub(crate) fn tunis_prayer_times() -> PrayerTimes {
+        let (tunis, params) = tunis_fixture();
+
+        PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{tunis_fixture, tunis_prayer_times};
+    use super::*;
+    use crate::models::adjustments::TimeAdjustmentBuilder;
+    use crate::models::fajr_floor::FajrFloor;
+    use crate::models::forbidden_durations::ForbiddenDurations;
+    use crate::models::high_latitude_rule::HighLatitudeRule;
+    use crate::models::iqamah::IqamahOffsetsBuilder;
+    use crate::models::parameters::ParametersBuilder;
+    use crate::models::rounding::Rounding;
+    use crate::models::twilight::Twilight;
+    use chrono::{TimeZone, Timelike};
+
+    #[test]
+    fn all_constructors_agree_on_the_same_day() {
+        let (tunis, params) = tunis_fixture();
+        let prayers = tunis_prayer_times();
+
+        assert_eq!(
+            PrayerTimes::from_naive(NaiveDate::from_ymd(2022, 8, 1), tunis, params),
+            prayers
+        );
+        assert_eq!(
+            PrayerTimes::at(Utc.ymd(2022, 8, 1).and_hms(17, 45, 12), tunis, params),
+            prayers
+        );
+    }
+
+    #[test]
+    fn is_time_for_prayer_at_boundaries() {
+        let prayers = tunis_prayer_times();
+        let asr = prayers.time(Prayer::Asr);
+        let maghrib = prayers.time(Prayer::Maghrib);
+
+        assert!(!prayers.is_time_for(Prayer::Asr, asr - Duration::seconds(1)));
+        assert!(prayers.is_time_for(Prayer::Asr, asr));
+        assert!(prayers.is_time_for(Prayer::Asr, maghrib - Duration::seconds(1)));
+        assert!(!prayers.is_time_for(Prayer::Asr, maghrib));
+        assert!(prayers.is_time_for(Prayer::Dhuhr, asr - Duration::seconds(1)));
+        assert!(!prayers.is_time_for(Prayer::Dhuhr, asr));
+    }
+
+    #[test]
+    fn is_time_for_fajr_tomorrow() {
+        let prayers = tunis_prayer_times();
+        let fajr_tomorrow = prayers.time(Prayer::FajrTomorrow);
+
+        assert!(prayers.is_time_for(Prayer::Qiyam, fajr_tomorrow - Duration::seconds(1)));
+        assert!(!prayers.is_time_for(Prayer::Qiyam, fajr_tomorrow));
+        assert!(!prayers.is_time_for(Prayer::FajrTomorrow, fajr_tomorrow - Duration::seconds(1)));
+        assert!(prayers.is_time_for(Prayer::FajrTomorrow, fajr_tomorrow));
+        assert!(prayers.is_time_for(Prayer::FajrTomorrow, fajr_tomorrow + Duration::hours(1)));
+    }
+
+    #[test]
+    fn upcoming_prayers_from_mid_afternoon() {
+        let prayers = tunis_prayer_times();
+        let from = Utc.ymd(2022, 8, 1).and_hms(14, 0, 0);
+        let upcoming = prayers.upcoming(from, 5);
+
+        assert_eq!(
+            upcoming,
+            vec![
+                (Prayer::Asr, prayers.time(Prayer::Asr)),
+                (Prayer::Maghrib, prayers.time(Prayer::Maghrib)),
+                (Prayer::Isha, prayers.time(Prayer::Isha)),
+                (
+                    Prayer::MiddleOfTheNight,
+                    prayers.time(Prayer::MiddleOfTheNight)
+                ),
+                (Prayer::Qiyam, prayers.time(Prayer::Qiyam)),
+            ]
+        );
+
+        // Rolls into the next day
+        let upcoming = prayers.upcoming(from, 7);
+        assert_eq!(upcoming.len(), 7);
+        assert_eq!(
+            upcoming[5],
+            (Prayer::Fajr, prayers.time(Prayer::FajrTomorrow))
+        );
+        assert_eq!(upcoming[6].0, Prayer::Sunrise);
+    }
+
+    #[test]
+    fn upcoming_prayers_after_the_day_ended() {
+        let prayers = tunis_prayer_times();
+        let from = Utc.ymd(2022, 8, 2).and_hms(12, 0, 0);
+        let upcoming = prayers.upcoming(from, 2);
+        let tomorrow =
+            PrayerTimes::new(Utc.ymd(2022, 8, 2), prayers.coordinates, prayers.parameters);
+
+        assert_eq!(
+            upcoming,
+            vec![
+                (Prayer::Asr, tomorrow.time(Prayer::Asr)),
+                (Prayer::Maghrib, tomorrow.time(Prayer::Maghrib)),
+            ]
+        );
+        assert!(prayers.upcoming(from, 0).is_empty());
+    }
+
+    #[test]
+    fn other_method_with_explicit_angles() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::with_angles(Method::Other, Madhab::Shafi, 18.0, 17.0);
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+        let times: Vec<_> = prayers
+            .iter()
+            .map(|(_, prayer_time)| prayer_time.datetime.unwrap())
+            .collect();
+
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(prayers.time(Prayer::Fajr) < prayers.time(Prayer::Sunrise) - Duration::hours(1));
+        assert!(prayers.time(Prayer::Isha) > prayers.time(Prayer::Maghrib) + Duration::hours(1));
+    }
+
+    #[test]
+    fn dhuhr_is_named_jumua_on_friday() {
+        let (tunis, params) = tunis_fixture();
+        let friday = PrayerTimes::new(Utc.ymd(2022, 8, 5), tunis, params);
+        let monday = tunis_prayer_times();
+
+        assert_eq!(friday.name(Prayer::Dhuhr), "Jumua");
+        assert_eq!(
+            friday.name_localized(Prayer::Dhuhr, Language::Arabic),
+            "الجمعة"
+        );
+        assert_eq!(friday.name(Prayer::Asr), "Asr");
+        assert_eq!(monday.name(Prayer::Dhuhr), "Dhuhr");
+    }
+
+    #[test]
+    fn is_jumua_on_friday_only() {
+        let (tunis, params) = tunis_fixture();
+        let friday = PrayerTimes::new(Utc.ymd(2022, 8, 5), tunis, params);
+        let saturday = PrayerTimes::new(Utc.ymd(2022, 8, 6), tunis, params);
+
+        assert!(friday.is_jumua());
+        assert!(!saturday.is_jumua());
+    }
+
+    #[test]
+    fn invalid_ordering_is_flagged() {
+        let sunrise = PrayerTime::new(Some(Utc.ymd(2022, 8, 1).and_hms(4, 57, 0)));
+        let maghrib = PrayerTime::new(Some(Utc.ymd(2022, 8, 1).and_hms(18, 40, 0)));
+
+        let fajr = ensure_before(
+            PrayerTime::new(Some(Utc.ymd(2022, 8, 1).and_hms(5, 10, 0))),
+            &sunrise,
+            FAJR_AFTER_SUNRISE_MESSAGE,
+        );
+        assert_eq!(fajr.code, PrayerTimeResolution::Invalid);
+        assert_eq!(fajr.message, FAJR_AFTER_SUNRISE_MESSAGE);
+        assert!(!fajr.is_valid());
+
+        let isha = ensure_after(
+            PrayerTime::new(Some(Utc.ymd(2022, 8, 1).and_hms(18, 30, 0))),
+            &maghrib,
+            ISHA_BEFORE_MAGHRIB_MESSAGE,
+        );
+        assert_eq!(isha.code, PrayerTimeResolution::Invalid);
+        assert_eq!(isha.message, ISHA_BEFORE_MAGHRIB_MESSAGE);
+
+        let isha = ensure_after(
+            PrayerTime::new(Some(Utc.ymd(2022, 8, 1).and_hms(20, 10, 0))),
+            &maghrib,
+            ISHA_BEFORE_MAGHRIB_MESSAGE,
+        );
+        assert_eq!(isha.code, PrayerTimeResolution::Normal);
+
+        let tunis = tunis_prayer_times();
+        assert!(tunis.fajr.is_valid());
+        assert!(tunis.isha.is_valid());
+    }
+
+    #[test]
+    fn isha_before_maghrib_is_flagged_at_high_latitude() {
+        // Around the summer solstice, the sun reaches the Jafari maghrib angle
+        // after the seventh of the night
+        let helsinki = Coordinates::new(60.1699, 24.9384);
+        let params =
+            ParametersBuilder::from(ParametersBuilder::with(Method::Jafari, Madhab::Shafi))
+                .high_latitude_rule(HighLatitudeRule::SeventhOfTheNight)
+                .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 6, 21), helsinki, params);
+
+        assert!(prayers.isha.datetime < prayers.maghrib.datetime);
+        assert_eq!(prayers.isha.code, PrayerTimeResolution::Invalid);
+        assert_eq!(prayers.isha.message, ISHA_BEFORE_MAGHRIB_MESSAGE);
+        for prayer in [Prayer::Fajr, Prayer::Dhuhr, Prayer::Asr, Prayer::Maghrib] {
+            assert_ne!(
+                prayers.prayer_time(prayer).code,
+                PrayerTimeResolution::Invalid
+            );
+        }
+    }
+
+    #[test]
+    fn maghrib_angle_delays_maghrib() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let mut builder =
+            ParametersBuilder::from(ParametersBuilder::with(Method::Karachi, Madhab::Shafi));
+        let at_sunset = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, builder.build());
+        let at_angle = PrayerTimes::new(
+            Utc.ymd(2022, 8, 1),
+            tunis,
+            builder.maghrib_angle(4.0).build(),
+        );
+        let delay = at_angle.time(Prayer::Maghrib) - at_sunset.time(Prayer::Maghrib);
+
+        assert!(delay > Duration::minutes(10));
+        assert!(delay < Duration::minutes(25));
+    }
+
+    #[test]
+    fn dhuhr_after_zenith_offset_is_applied() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::from_method(Method::Karachi, Madhab::Shafi)
+            .dhuhr_after_zenith_seconds(150)
+            .rounding(Rounding::None)
+            .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+        let transit = SolarTime::new(prayers.date, tunis).transit.unwrap();
+
+        assert_eq!(
+            prayers.time(Prayer::Dhuhr) - transit,
+            Duration::seconds(150)
+        );
+    }
+
+    #[test]
+    fn from_solar_time_matches_new() {
+        let (tunis, params) = tunis_fixture();
+        let date = Utc.ymd(2022, 8, 1);
+        let solar_time = SolarTime::new(date.and_hms(0, 0, 0), tunis);
+        let solar_time_tomorrow = SolarTime::new(date.succ().and_hms(0, 0, 0), tunis);
+        let prayers =
+            PrayerTimes::from_solar_time(date, tunis, params, solar_time, solar_time_tomorrow);
+        let expected = tunis_prayer_times();
+
+        for prayer in Prayer::all() {
+            assert_eq!(prayers.prayer_time(prayer), expected.prayer_time(prayer));
+        }
+    }
+
+    #[test]
+    fn from_solar_time_with_short_night_applies_high_latitude_rule() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi)
+            .high_latitude_rule(HighLatitudeRule::MiddleOfTheNight)
+            .high_latitude_threshold(30.0)
+            .rounding(Rounding::None)
+            .build();
+        let date = Utc.ymd(2022, 8, 1);
+        let solar_time = SolarTime::new(date.and_hms(0, 0, 0), tunis);
+        let mut solar_time_tomorrow = SolarTime::new(date.succ().and_hms(0, 0, 0), tunis);
+
+        let prayers =
+            PrayerTimes::from_solar_time(date, tunis, params, solar_time, solar_time_tomorrow);
+        assert_eq!(prayers.fajr.code, PrayerTimeResolution::Normal);
+
+        // A two hours night, Fajr can't be before its middle
+        solar_time_tomorrow.sunrise = solar_time.sunset.map(|sunset| sunset + Duration::hours(2));
+        let prayers =
+            PrayerTimes::from_solar_time(date, tunis, params, solar_time, solar_time_tomorrow);
+        assert_eq!(prayers.fajr.code, PrayerTimeResolution::HighLatitudeRule);
+        assert_eq!(
+            prayers.fajr.datetime,
+            solar_time
+                .sunrise
+                .map(|sunrise| sunrise - Duration::hours(1))
+        );
+    }
+
+    #[test]
+    fn atmosphere_shifts_sunrise() {
+        let coordinates = Coordinates::new(65.0, 10.75);
+        let mut builder = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi);
+        let date = Utc.ymd(2022, 12, 10);
+        let standard = PrayerTimes::new(date, coordinates, builder.build());
+        let cold = PrayerTimes::new(date, coordinates, builder.atmosphere(1050.0, -20.0).build());
+
+        assert_eq!(
+            standard.time(Prayer::Sunrise) - cold.time(Prayer::Sunrise),
+            Duration::minutes(2)
+        );
+        assert_eq!(cold.time(Prayer::Dhuhr), standard.time(Prayer::Dhuhr));
+    }
+
+    #[test]
+    fn current_prayer_at_given_time() {
+        let prayers = tunis_prayer_times();
+        let before_fajr = prayers.fajr.datetime.unwrap() - Duration::minutes(1);
+        let after_dhuhr = prayers.dhuhr.datetime.unwrap() + Duration::minutes(1);
+
+        assert_eq!(prayers.current_at(before_fajr), None);
+        assert_eq!(
+            prayers.current_at(prayers.fajr.datetime.unwrap()),
+            Some(Prayer::Fajr)
+        );
+        assert_eq!(prayers.current_at(after_dhuhr), Some(Prayer::Dhuhr));
+    }
+
+    #[test]
+    fn next_prayer_at_given_time() {
+        let prayers = tunis_prayer_times();
+        let after_dhuhr = prayers.dhuhr.datetime.unwrap() + Duration::minutes(1);
+        let after_isha = prayers.isha.datetime.unwrap() + Duration::minutes(1);
+
+        assert_eq!(prayers.next_at(after_dhuhr), Some(Prayer::Asr));
+        assert_eq!(prayers.next_at(after_isha), Some(Prayer::MiddleOfTheNight));
+    }
+
+    #[test]
+    fn no_current_nor_next_prayer_before_fajr() {
+        let prayers = tunis_prayer_times();
+        let before_fajr = prayers.fajr.datetime.unwrap() - Duration::hours(1);
+
+        assert_eq!(prayers.current_at(before_fajr), None);
+        assert_eq!(prayers.next_at(before_fajr), None);
+        assert_eq!(prayers.time_remaining_at(before_fajr), (1, 0));
+    }
+
+    #[test]
+    fn iterate_prayers_in_chronological_order() {
+        let prayers = tunis_prayer_times();
+        let names: Vec<Prayer> = prayers.iter().map(|(prayer, _)| prayer).collect();
+        let times: Vec<DateTime<Utc>> = prayers
+            .iter()
+            .map(|(_, prayer_time)| prayer_time.datetime.unwrap())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                Prayer::Fajr,
+                Prayer::Sunrise,
+                Prayer::Dhuhr,
+                Prayer::Asr,
+                Prayer::Maghrib,
+                Prayer::Isha,
+                Prayer::MiddleOfTheNight,
+                Prayer::Qiyam,
+            ]
+        );
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn fajr_tomorrow_matches_next_day_fajr() {
+        let params = ParametersBuilder::with(Method::MoonsightingCommittee, Madhab::Shafi);
+        let london = Coordinates::new(51.5074, -0.1278);
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 4, 15), london, params);
+        let tomorrow = PrayerTimes::new(Utc.ymd(2022, 4, 16), london, params);
+
+        assert_eq!(prayers.fajr_tomorrow, tomorrow.fajr);
+        assert_eq!(
+            prayers.fajr_tomorrow.datetime.unwrap().date(),
+            Utc.ymd(2022, 4, 16)
+        );
+    }
+
+    #[test]
+    fn time_remaining_at_given_time() {
+        let prayers = tunis_prayer_times();
+        let time = prayers.asr.datetime.unwrap() - Duration::minutes(90);
+
+        assert_eq!(prayers.time_remaining_at(time), (1, 30));
+    }
+
+    #[test]
+    fn duration_until_next_prayer() {
+        let prayers = tunis_prayer_times();
+        let from = Utc.ymd(2022, 8, 1).and_hms(13, 5, 42);
+        assert_eq!(
+            prayers.duration_until_next(from),
+            prayers.time(Prayer::Asr) - from
+        );
+
+        // After Qiyam, the next prayer is tomorrow's Fajr
+        let from = prayers.time(Prayer::Qiyam) + Duration::seconds(30);
+        assert_eq!(prayers.next_at(from), Some(Prayer::FajrTomorrow));
+        assert_eq!(
+            prayers.duration_until_next(from),
+            prayers.time(Prayer::FajrTomorrow) - from
+        );
+
+        let from = prayers.time(Prayer::FajrTomorrow) + Duration::minutes(1);
+        assert_eq!(prayers.duration_until_next(from), Duration::zero());
+    }
+
+    #[test]
+    fn time_remaining_before_tomorrow_fajr() {
+        let prayers = tunis_prayer_times();
+        let time = prayers.fajr_tomorrow.datetime.unwrap() - Duration::minutes(125);
+
+        assert_eq!(prayers.next_at(time), Some(Prayer::FajrTomorrow));
+        assert_eq!(prayers.time_remaining_at(time), (2, 5));
+    }
+
+    #[test]
+    fn time_remaining_does_not_wrap_after_tomorrow_fajr() {
+        let prayers = tunis_prayer_times();
+        let time = prayers.fajr_tomorrow.datetime.unwrap() + Duration::minutes(10);
+
+        assert_eq!(prayers.current_at(time), Some(Prayer::FajrTomorrow));
+        assert_eq!(prayers.next_at(time), None);
+        assert_eq!(prayers.time_remaining_at(time), (0, 0));
+    }
+
+    #[test]
+    fn imsak_before_fajr() {
+        let prayers = tunis_prayer_times();
+
+        assert_eq!(
+            prayers
+                .fajr
+                .datetime
+                .unwrap()
+                .signed_duration_since(prayers.time(Prayer::Imsak)),
+            Duration::minutes(prayers.parameters.imsak_interval as i64)
+        );
+
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::new(18.0, 17.0)
+            .imsak_interval(15)
+            .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+
+        assert_eq!(
+            prayers.time(Prayer::Fajr) - prayers.time(Prayer::Imsak),
+            Duration::minutes(15)
+        );
+    }
+
+    #[test]
+    fn duha_after_sunrise() {
+        let prayers = tunis_prayer_times();
+
+        assert_eq!(
+            prayers.time(Prayer::Duha) - prayers.time(Prayer::Sunrise),
+            Duration::minutes(15)
+        );
+        assert!(prayers.time(Prayer::Duha) < prayers.time(Prayer::Dhuhr));
+    }
+
+    #[test]
+    fn custom_asr_shadow_ratio_matches_hanafi() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let hanafi = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Hanafi);
+        let custom = ParametersBuilder::from(ParametersBuilder::with(
+            Method::MuslimWorldLeague,
+            Madhab::Shafi,
+        ))
+        .asr_shadow_ratio(2.0)
+        .build();
+        let hanafi_prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, hanafi);
+        let custom_prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, custom);
+
+        assert_eq!(custom_prayers.asr, hanafi_prayers.asr);
+        assert!(custom_prayers.asr != tunis_prayer_times().asr);
+    }
+
+    #[test]
+    fn default_prayer_times_are_rounded_to_the_minute() {
+        let prayers = tunis_prayer_times();
+        let mut prayer_times: Vec<PrayerTime> = Prayer::value_variants()
+            .iter()
+            .map(|prayer| prayers.prayer_time(*prayer))
+            .collect();
+        prayer_times.push(prayers.solar_sunrise.clone());
+        prayer_times.push(prayers.solar_sunset.clone());
+
+        for prayer_time in prayer_times {
+            let datetime = prayer_time.datetime.unwrap();
+            assert_eq!((datetime.second(), datetime.nanosecond()), (0, 0));
+        }
+    }
+
+    #[test]
+    fn rounding_applies_to_all_prayer_times() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::from(ParametersBuilder::with(
+            Method::MuslimWorldLeague,
+            Madhab::Shafi,
+        ))
+        .rounding(Rounding::Up)
+        .build();
+        let rounded_up = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+        let nearest = tunis_prayer_times();
+
+        for (prayer, prayer_time) in rounded_up.iter() {
+            assert_eq!(prayer_time.datetime.unwrap().second(), 0);
+            assert!(prayer_time.datetime.unwrap() >= nearest.time(prayer));
+        }
+    }
+
+    #[test]
+    fn solar_midnight_is_centered_on_the_local_night() {
+        // Local solar midnight in Honolulu is around 10:30 UTC
+        let honolulu = Coordinates::new(21.3069, -157.8583);
+        let date = Utc.ymd(2022, 8, 1);
+        let mut builder = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi);
+        let by_maghrib = PrayerTimes::new(date, honolulu, builder.build());
+        let by_transit = PrayerTimes::new(date, honolulu, builder.solar_midnight(true).build());
+
+        let solar_time = SolarTime::new(date.and_hms(0, 0, 0), honolulu);
+        let solar_time_tomorrow = SolarTime::new(date.succ().and_hms(0, 0, 0), honolulu);
+        let transit = solar_time.transit.unwrap();
+        let solar_midnight = transit + (solar_time_tomorrow.transit.unwrap() - transit) / 2;
+        let middle_of_the_night = by_transit.time(Prayer::MiddleOfTheNight);
+
+        assert_eq!(middle_of_the_night, Rounding::Nearest.round(solar_midnight));
+        assert_eq!(middle_of_the_night.date(), date.succ());
+        assert_eq!(middle_of_the_night.hour(), 10);
+        // Half the night between Maghrib and Fajr falls before the solar midnight,
+        // as the morning twilight is longer than the time between sunset and Maghrib
+        assert!(by_maghrib.time(Prayer::MiddleOfTheNight) < middle_of_the_night);
+        assert!(middle_of_the_night < by_transit.time(Prayer::Qiyam));
+        assert!(by_transit.time(Prayer::Qiyam) < by_transit.time(Prayer::FajrTomorrow));
+        assert_eq!(
+            by_transit.time(Prayer::Maghrib),
+            by_maghrib.time(Prayer::Maghrib)
+        );
+    }
+
+    #[test]
+    fn fajr_interval_before_sunrise() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi)
+            .fajr_interval(75)
+            .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+
+        assert_eq!(
+            prayers.time(Prayer::Fajr),
+            prayers.time(Prayer::Sunrise) - Duration::minutes(75)
+        );
+        assert_eq!(prayers.isha, tunis_prayer_times().isha);
+    }
+
+    #[test]
+    fn mean_solar_time_without_equation_of_time() {
+        // The equation of time is about 16 minutes early November
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let date = Utc.ymd(2022, 11, 3);
+        let mut builder = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi);
+        let apparent = PrayerTimes::new(date, tunis, builder.build());
+        let mean = PrayerTimes::new(date, tunis, builder.use_equation_of_time(false).build());
+
+        for prayer in [
+            Prayer::Fajr,
+            Prayer::Sunrise,
+            Prayer::Dhuhr,
+            Prayer::Asr,
+            Prayer::Isha,
+        ] {
+            let shift = mean.time(prayer) - apparent.time(prayer);
+            assert!((16..=17).contains(&shift.num_minutes()), "{:?}", prayer);
+        }
+        let zenith_shift = mean.forbidden_windows()[1].1 - apparent.forbidden_windows()[1].1;
+        assert_eq!(zenith_shift.num_minutes(), 16);
+    }
+
+    #[test]
+    fn unresolved_polar_day() {
+        let tromso = Coordinates::new(69.65, 18.96);
+        let params = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi)
+            .polar_circle_resolution(PolarCircleResolution::Unresolved)
+            .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 6, 21), tromso, params);
+
+        for (_, prayer_time) in prayers.iter() {
+            assert_eq!(prayer_time.datetime, None);
+            assert_eq!(prayer_time.code, PrayerTimeResolution::Invalid);
+            assert_eq!(prayer_time.message, UNRESOLVED_POLAR_CIRCLE_MESSAGE);
+        }
+        assert_eq!(prayers.day_type(), DayType::MidnightSun);
+    }
+
+    #[test]
+    fn unresolved_polar_circle_all_year() {
+        let coordinates = Coordinates::new(85.0, 18.96);
+        let params = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi)
+            .polar_circle_resolution(PolarCircleResolution::Unresolved)
+            .build();
+
+        for prayers in PrayerTimes::year_iter(2022, coordinates, params) {
+            if prayers.day_type() != DayType::Normal {
+                assert_eq!(prayers.sunrise.datetime, None);
+                assert_eq!(prayers.sunrise.code, PrayerTimeResolution::Invalid);
+            }
+        }
+    }
+
+    #[test]
+    fn current_prayer_skips_unknown_times() {
+        // In London around the summer solstice, the sun does not reach the Fajr and Isha angles
+        let london = Coordinates::new(51.5074, -0.1278);
+        let params = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi)
+            .high_latitude_threshold(60.0)
+            .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 6, 21), london, params);
+        assert_eq!(prayers.isha.datetime, None);
+
+        let after_asr = prayers.time(Prayer::Asr) + Duration::minutes(5);
+        assert_eq!(prayers.current_at(after_asr), Some(Prayer::Asr));
+        assert_eq!(prayers.next_at(after_asr), Some(Prayer::Maghrib));
+
+        let after_maghrib = prayers.time(Prayer::Maghrib) + Duration::hours(2);
+        assert_eq!(prayers.current_at(after_maghrib), Some(Prayer::Maghrib));
+    }
+
+    #[test]
+    fn time_remaining_skips_unknown_times() {
+        let london = Coordinates::new(51.5074, -0.1278);
+        let params = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi)
+            .high_latitude_threshold(60.0)
+            .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 6, 21), london, params);
+        assert_eq!(prayers.fajr.datetime, None);
+        assert_eq!(prayers.isha.datetime, None);
+
+        // Before the unknown Fajr, the next known prayer is sunrise
+        let before_fajr = Utc.ymd(2022, 6, 21).and_hms(1, 0, 0);
+        assert_eq!(
+            prayers.duration_until_next(before_fajr),
+            prayers.time(Prayer::Sunrise) - before_fajr
+        );
+
+        let after_asr = prayers.time(Prayer::Asr) + Duration::minutes(5);
+        assert_eq!(
+            prayers.duration_until_next(after_asr),
+            prayers.time(Prayer::Maghrib) - after_asr
+        );
+
+        // The night prayers and tomorrow's Fajr are unknown as well
+        let after_maghrib = prayers.time(Prayer::Maghrib) + Duration::minutes(5);
+        assert_eq!(prayers.duration_until_next(after_maghrib), Duration::zero());
+        assert_eq!(prayers.time_remaining_at(after_maghrib), (0, 0));
+    }
+
+    #[test]
+    fn fajr_angle_never_reached_without_fajr_floor() {
+        // In London around the summer solstice, the sun does not go below 18°
+        let london = Coordinates::new(51.5074, -0.1278);
+        let date = Utc.ymd(2022, 6, 21);
+        let mut builder = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi);
+        builder.fajr_floor(FajrFloor::None);
+        let no_floor = builder.build();
+        let solar_midnight = builder.solar_midnight(true).build();
+        let above_threshold =
+            ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi)
+                .high_latitude_threshold(60.0)
+                .build();
+
+        for params in [no_floor, solar_midnight, above_threshold] {
+            let prayers = PrayerTimes::new(date, london, params);
+
+            assert_eq!(prayers.fajr.datetime, None);
+            assert_eq!(prayers.fajr_tomorrow.datetime, None);
+            assert_eq!(prayers.middle_of_the_night.datetime, None);
+            assert_eq!(prayers.qiyam.datetime, None);
+            assert!(prayers.maghrib.is_valid());
+        }
+    }
+
+    #[test]
+    fn prayer_times_rounded_to_five_minutes() {
+        let prayers = tunis_prayer_times();
+        let rounded = prayers.rounded_to(5).unwrap();
+
+        for (prayer, prayer_time) in rounded.iter() {
+            let datetime = prayer_time.datetime.unwrap();
+            assert_eq!(datetime.minute() % 5, 0, "{:?} at {}", prayer, datetime);
+            assert_eq!(datetime.second(), 0);
+
+            let shift = datetime - prayers.time(prayer);
+            assert!(shift.num_seconds().abs() <= 150);
+        }
+        assert_eq!(rounded.parameters, prayers.parameters);
+        assert_eq!(prayers.rounded_to(0), None);
+    }
+
+    #[test]
+    fn fajr_floor_at_mid_latitude_in_june() {
+        let chicago = Coordinates::new(42.0, -87.6);
+        let date = Utc.ymd(2022, 6, 21);
+        let mut builder = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi);
+        builder.high_latitude_rule(HighLatitudeRule::SeventhOfTheNight);
+        let high_latitude_only = PrayerTimes::new(date, chicago, builder.build());
+        let always = PrayerTimes::new(date, chicago, builder.fajr_floor(FajrFloor::Always).build());
+        let none = PrayerTimes::new(date, chicago, builder.fajr_floor(FajrFloor::None).build());
+
+        assert_eq!(high_latitude_only.fajr.code, PrayerTimeResolution::Normal);
+        assert_eq!(high_latitude_only.fajr, none.fajr);
+        assert_eq!(always.fajr.code, PrayerTimeResolution::HighLatitudeRule);
+        assert!(always.time(Prayer::Fajr) > high_latitude_only.time(Prayer::Fajr));
+
+        // A seventh of the night before sunrise
+        let night = always.night_duration().unwrap();
+        let fajr = always.time(Prayer::Sunrise) - night / 7;
+        assert!((always.time(Prayer::Fajr) - fajr).num_minutes().abs() <= 1);
+    }
+
+    #[test]
+    fn diff_prayer_times_after_an_adjustment() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let date = Utc.ymd(2022, 8, 1);
+        let mut builder = ParametersBuilder::from_method(Method::MuslimWorldLeague, Madhab::Shafi);
+        let prayers = PrayerTimes::new(date, tunis, builder.build());
+        let adjusted = builder
+            .adjustments(TimeAdjustmentBuilder::new().fajr(5).build())
+            .build();
+        let adjusted_prayers = PrayerTimes::new(date, tunis, adjusted);
+
+        // Imsak follows Fajr, and the night ends at tomorrow's Fajr, which is adjusted as well
+        assert_eq!(
+            prayers.diff(&adjusted_prayers),
+            vec![
+                (Prayer::Imsak, Duration::minutes(5)),
+                (Prayer::Fajr, Duration::minutes(5)),
+                (Prayer::MiddleOfTheNight, Duration::minutes(2)),
+                (Prayer::Qiyam, Duration::minutes(3)),
+                (Prayer::FajrTomorrow, Duration::minutes(5)),
+            ]
+        );
+        assert_eq!(
+            adjusted_prayers.diff(&prayers)[1],
+            (Prayer::Fajr, Duration::minutes(-5))
+        );
+        assert!(prayers.diff(&prayers).is_empty());
+    }
+
+    #[test]
+    fn diff_prayer_times_of_consecutive_days() {
+        let prayers = tunis_prayer_times();
+        let tomorrow =
+            PrayerTimes::new(Utc.ymd(2022, 8, 2), prayers.coordinates, prayers.parameters);
+        let diff = prayers.diff(&tomorrow);
+
+        // Days get shorter in August, Maghrib is earlier by about a minute
+        let maghrib = diff
+            .iter()
+            .find(|(prayer, _)| *prayer == Prayer::Maghrib)
+            .unwrap();
+        assert_eq!(maghrib.1, Duration::minutes(-1));
+        assert!(diff.iter().all(|(_, shift)| shift.num_minutes().abs() <= 2));
+
+        let mut unknown = tomorrow.clone();
+        unknown.isha = PrayerTime::new(None);
+        assert!(prayers
+            .diff(&unknown)
+            .iter()
+            .all(|(prayer, _)| *prayer != Prayer::Isha));
+    }
+
+    #[test]
+    fn asr_for_both_madhabs() {
+        let prayers = tunis_prayer_times();
+        let shafi = prayers.asr_for(Madhab::Shafi);
+        let hanafi = prayers.asr_for(Madhab::Hanafi);
+
+        assert_eq!(shafi, prayers.time(Prayer::Asr));
+        assert!(hanafi > shafi);
+
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Hanafi);
+        let hanafi_prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+        assert_eq!(hanafi, hanafi_prayers.time(Prayer::Asr));
+    }
+
+    #[test]
+    fn qiyam_adjustment_shifts_qiyam_time() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::from(ParametersBuilder::with(
+            Method::MuslimWorldLeague,
+            Madhab::Shafi,
+        ))
+        .adjustments(TimeAdjustmentBuilder::new().qiyam(10).build())
+        .build();
+        let adjusted = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+        let unadjusted = tunis_prayer_times();
+
+        assert_eq!(
+            adjusted.time(Prayer::Qiyam),
+            unadjusted.time(Prayer::Qiyam) + Duration::minutes(10)
+        );
+        assert_eq!(
+            adjusted.time(Prayer::MiddleOfTheNight),
+            unadjusted.time(Prayer::MiddleOfTheNight)
+        );
+    }
+
+    #[test]
+    fn solar_midnight_between_maghrib_and_fajr_tomorrow() {
+        let prayers = tunis_prayer_times();
+        let solar_midnight = prayers.solar_midnight();
+
+        assert!(solar_midnight > prayers.time(Prayer::Maghrib));
+        assert!(solar_midnight < prayers.time(Prayer::FajrTomorrow));
+    }
+
+    #[test]
+    fn forbidden_windows_are_ordered() {
+        let prayers = tunis_prayer_times();
+        let windows = prayers.forbidden_windows();
+
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0].0, prayers.time(Prayer::Sunrise));
+        assert_eq!(windows[0].1 - windows[0].0, Duration::minutes(15));
+        assert_eq!(windows[1].1 - windows[1].0, Duration::minutes(5));
+        assert_eq!(windows[2].1, prayers.solar_sunset.datetime.unwrap());
+        for window in windows.windows(2) {
+            assert!(window[0].1 <= window[1].0);
+        }
+        assert!(windows[1].1 <= prayers.time(Prayer::Dhuhr));
+    }
+
+    #[test]
+    fn forbidden_windows_durations_are_configurable() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let params = ParametersBuilder::from(ParametersBuilder::with(
+            Method::MuslimWorldLeague,
+            Madhab::Shafi,
+        ))
+        .forbidden_durations(ForbiddenDurations::new(20, 10, 12))
+        .build();
+        let windows = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params).forbidden_windows();
+
+        assert_eq!(windows[0].1 - windows[0].0, Duration::minutes(20));
+        assert_eq!(windows[1].1 - windows[1].0, Duration::minutes(10));
+        assert_eq!(windows[2].1 - windows[2].0, Duration::minutes(12));
+    }
+
+    #[test]
+    fn iqamah_time_after_adhan() {
+        let tunis = Coordinates::new(36.8065, 10.1815);
+        let offsets = IqamahOffsetsBuilder::new().dhuhr(15).maghrib(5).build();
+        let params = ParametersBuilder::from(ParametersBuilder::with(
+            Method::MuslimWorldLeague,
+            Madhab::Shafi,
+        ))
+        .iqamah_offsets(offsets)
+        .build();
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+
+        assert_eq!(
+            prayers.iqamah_time(Prayer::Dhuhr).unwrap() - prayers.time(Prayer::Dhuhr),
+            Duration::minutes(15)
+        );
+        assert_eq!(
+            prayers.iqamah_time(Prayer::Maghrib).unwrap() - prayers.time(Prayer::Maghrib),
+            Duration::minutes(5)
+        );
+        assert_eq!(
+            prayers.iqamah_time(Prayer::Asr),
+            Some(prayers.time(Prayer::Asr))
+        );
+        assert_eq!(prayers.iqamah_time(Prayer::Sunrise), None);
+        assert_eq!(prayers.iqamah_time(Prayer::Qiyam), None);
+    }
+
+    #[test]
+    fn angle_based_fajr_is_earlier_than_seventh_of_the_night_at_55n() {
+        let newcastle = Coordinates::new(55.0, -1.6);
+        let date = Utc.ymd(2022, 6, 21);
+        let mut params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        params.high_latitude_rule = HighLatitudeRule::SeventhOfTheNight;
+        let seventh = PrayerTimes::new(date, newcastle, params);
+        params.high_latitude_rule = HighLatitudeRule::AngleBased;
+        let angle_based = PrayerTimes::new(date, newcastle, params);
+
+        assert_eq!(
+            angle_based.fajr.code,
+            PrayerTimeResolution::HighLatitudeRule
+        );
+        assert!(angle_based.time(Prayer::Fajr) < seventh.time(Prayer::Fajr));
+        assert!(angle_based.time(Prayer::Isha) > seventh.time(Prayer::Isha));
+        assert_eq!(
+            angle_based.time(Prayer::Sunrise),
+            seventh.time(Prayer::Sunrise)
+        );
+    }
+
+    #[test]
+    fn lower_high_latitude_threshold() {
+        let geneva = Coordinates::new(46.2044, 6.1432);
+        let date = Utc.ymd(2022, 6, 21);
+        let mut params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        params.high_latitude_rule = HighLatitudeRule::SeventhOfTheNight;
+        let prayers = PrayerTimes::new(date, geneva, params);
+
+        assert_eq!(prayers.fajr.code, PrayerTimeResolution::Normal);
+
+        params.high_latitude_threshold = 45.0;
+        let prayers = PrayerTimes::new(date, geneva, params);
+
+        assert_eq!(prayers.fajr.code, PrayerTimeResolution::HighLatitudeRule);
+        assert_eq!(prayers.isha.code, PrayerTimeResolution::HighLatitudeRule);
+    }
+
+    #[test]
+    fn high_latitude_rule_in_the_southern_hemisphere() {
+        // Ushuaia around the December solstice, the mirror of a northern summer
+        let ushuaia = Coordinates::new(-54.8019, -68.303);
+        let date = Utc.ymd(2022, 12, 21);
+        let mut params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        params.high_latitude_rule = HighLatitudeRule::SeventhOfTheNight;
+        let prayers = PrayerTimes::new(date, ushuaia, params);
+
+        assert!(params.is_high_latitude(ushuaia));
+        assert_eq!(prayers.fajr.code, PrayerTimeResolution::HighLatitudeRule);
+        assert_eq!(prayers.isha.code, PrayerTimeResolution::HighLatitudeRule);
+        assert_eq!(
+            HighLatitudeRule::recommended(ushuaia),
+            HighLatitudeRule::SeventhOfTheNight
+        );
+    }
+
+    #[test]
+    fn white_twilight_isha_is_later_than_red_twilight() {
+        let (tunis, mut params) = tunis_fixture();
+        let red = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+        params.twilight = Twilight::White;
+        let white = PrayerTimes::new(Utc.ymd(2022, 8, 1), tunis, params);
+
+        assert!(white.time(Prayer::Isha) > red.time(Prayer::Isha));
+        assert_eq!(white.time(Prayer::Maghrib), red.time(Prayer::Maghrib));
+    }
+
+    #[test]
+    fn white_twilight_season_adjusted_isha_for_moonsighting_committee() {
+        let london = Coordinates::new(51.5074, -0.1278);
+        let mut params = ParametersBuilder::with(Method::MoonsightingCommittee, Madhab::Shafi);
+        let red = PrayerTimes::new(Utc.ymd(2022, 4, 15), london, params);
+        params.twilight = Twilight::White;
+        let white = PrayerTimes::new(Utc.ymd(2022, 4, 15), london, params);
+
+        assert!(white.time(Prayer::Isha) > red.time(Prayer::Isha));
+    }
+
+    #[test]
+    fn nearest_day_polar_circle_resolution_in_midsummer() {
+        let tromso = Coordinates::new(69.649208, 18.955324);
+        let mut params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        params.polar_circle_resolution = PolarCircleResolution::NearestDay;
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 6, 21), tromso, params);
+        let sunrise = prayers.time(Prayer::Sunrise);
+        let maghrib = prayers.time(Prayer::Maghrib);
+
+        assert_eq!(prayers.sunrise.code, PrayerTimeResolution::PolarCircle);
+        assert_eq!(prayers.maghrib.code, PrayerTimeResolution::PolarCircle);
+        assert_eq!(prayers.sunrise.message, POLAR_CIRCLE_RESOLUTION_MESSAGE);
+        assert_eq!(prayers.time(Prayer::Dhuhr).date(), Utc.ymd(2022, 6, 21));
+        assert!(sunrise < prayers.time(Prayer::Dhuhr));
+        assert!(prayers.time(Prayer::Dhuhr) < maghrib);
+        assert!(prayers.time(Prayer::Fajr) < sunrise);
+        assert!(maghrib < prayers.time(Prayer::Isha));
+    }
+
+    #[test]
+    fn day_type_in_tromso() {
+        let tromso = Coordinates::new(69.649208, 18.955324);
+        let params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+
+        assert_eq!(
+            PrayerTimes::new(Utc.ymd(2022, 6, 21), tromso, params).day_type(),
+            DayType::MidnightSun
+        );
+        assert_eq!(
+            PrayerTimes::new(Utc.ymd(2022, 12, 21), tromso, params).day_type(),
+            DayType::PolarNight
+        );
+        assert_eq!(tunis_prayer_times().day_type(), DayType::Normal);
+    }
+
+    #[test]
+    fn day_length_and_fasting_duration() {
+        let prayers = tunis_prayer_times();
+        let day_length = prayers.day_length().unwrap();
+        let fasting_duration = prayers.fasting_duration().unwrap();
+
+        assert_eq!(
+            day_length,
+            prayers.solar_sunset.datetime.unwrap() - prayers.solar_sunrise.datetime.unwrap()
+        );
+        assert_eq!(
+            fasting_duration,
+            prayers.time(Prayer::Maghrib) - prayers.time(Prayer::Fajr)
+        );
+        assert!(fasting_duration > day_length);
+        assert!(day_length > Duration::hours(13) && day_length < Duration::hours(15));
+    }
+
+    #[test]
+    fn night_duration_from_sunset_to_next_sunrise() {
+        let prayers = tunis_prayer_times();
+        let tomorrow =
+            PrayerTimes::new(Utc.ymd(2022, 8, 2), prayers.coordinates, prayers.parameters);
+        let night_duration = prayers.night_duration().unwrap();
+
+        assert_eq!(
+            night_duration,
+            tomorrow.solar_sunrise.datetime.unwrap() - prayers.solar_sunset.datetime.unwrap()
+        );
+        // Sunrise is about a minute later each day in August
+        let full_day = night_duration + prayers.day_length().unwrap();
+        assert!(full_day - Duration::days(1) <= Duration::minutes(2));
+        assert!(full_day >= Duration::days(1));
+    }
+
+    #[test]
+    fn no_day_length_during_polar_day() {
+        let tromso = Coordinates::new(69.649208, 18.955324);
+        let params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 6, 21), tromso, params);
+
+        assert_eq!(prayers.day_length(), None);
+        assert_eq!(prayers.fasting_duration(), None);
+    }
+
+    #[test]
+    fn nearest_town_polar_circle_resolution() {
+        let longyearbyen = Coordinates::new(78.2232, 15.6267);
+        let mut params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+        params.polar_circle_resolution = PolarCircleResolution::NearestTown;
+        let date = Utc.ymd(2022, 6, 21);
+        let prayers = PrayerTimes::new(date, longyearbyen, params);
+        let solar_time = SolarTime::new(
+            date.and_hms(0, 0, 0),
+            Coordinates::new(NEAREST_TOWN_LATITUDE, 15.6267),
+        );
+
+        assert_eq!(prayers.solar_sunrise.datetime, solar_time.sunrise);
+        assert_eq!(prayers.solar_sunset.datetime, solar_time.sunset);
+        assert_eq!(
+            prayers.time(Prayer::Asr),
+            solar_time
+                .afternoon(1.0)
+                .adjust_time(params.time_adjustments(Prayer::Asr))
+        );
+        assert_eq!(prayers.dhuhr.code, PrayerTimeResolution::PolarCircle);
+        assert_eq!(prayers.isha.code, PrayerTimeResolution::PolarCircle);
+    }
+
+    #[cfg(feature = "hijri")]
+    #[test]
+    fn hijri_date_of_prayer_times() {
+        let prayers = tunis_prayer_times();
+
+        assert_eq!(prayers.hijri_date(), HijriDate::new(1444, 1, 3));
+    }
+
+    #[cfg(feature = "hijri")]
+    #[test]
+    fn umm_al_qura_isha_is_delayed_during_ramadan() {
+        let makkah = Coordinates::new(21.4225241, 39.8261818);
+        let params =
+            ParametersBuilder::from(ParametersBuilder::with(Method::UmmAlQura, Madhab::Shafi))
+                .ramadan(true)
+                .build();
+
+        // 1444/9/3
+        let prayers = PrayerTimes::new(Utc.ymd(2023, 3, 25), makkah, params);
+        assert_eq!(
+            prayers.time(Prayer::Isha) - prayers.time(Prayer::Maghrib),
+            Duration::minutes(120)
+        );
+
+        // 1444/10/11
+        let prayers = PrayerTimes::new(Utc.ymd(2023, 5, 1), makkah, params);
+        assert_eq!(
+            prayers.time(Prayer::Isha) - prayers.time(Prayer::Maghrib),
+            Duration::minutes(90)
+        );
+    }
+
+    #[test]
+    fn jafari_prayer_times_in_qom() {
+        let qom = Coordinates::new(34.6401, 50.8764);
+        let params = ParametersBuilder::with(Method::Jafari, Madhab::Shafi);
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 1), qom, params);
+
+        // Maghrib is when the sun is 4 degrees below the horizon
+        assert_eq!(
+            prayers.solar_sunset.datetime.unwrap(),
+            Utc.ymd(2022, 8, 1).and_hms(15, 39, 0)
+        );
+
+        assert_eq!(
+            prayers.fajr.datetime.unwrap(),
+            Utc.ymd(2022, 8, 1).and_hms(0, 22, 0)
+        );
+        assert_eq!(
+            prayers.sunrise.datetime.unwrap(),
+            Utc.ymd(2022, 8, 1).and_hms(1, 46, 0)
+        );
+        assert_eq!(
+            prayers.dhuhr.datetime.unwrap(),
+            Utc.ymd(2022, 8, 1).and_hms(8, 43, 0)
+        );
+        assert_eq!(
+            prayers.maghrib.datetime.unwrap(),
+            Utc.ymd(2022, 8, 1).and_hms(15, 56, 0)
+        );
+        assert_eq!(
+            prayers.isha.datetime.unwrap(),
+            Utc.ymd(2022, 8, 1).and_hms(16, 51, 0)
+        );
+        assert_eq!(
+            prayers.middle_of_the_night.datetime.unwrap(),
+            Utc.ymd(2022, 8, 1).and_hms(20, 1, 0)
+        );
+    }
+
+    #[test]
+    fn turkey_prayer_times_in_istanbul() {
+        let istanbul = Coordinates::new(41.0082, 28.9784);
+        let params = ParametersBuilder::with(Method::Turkey, Madhab::Shafi);
+        let prayers = PrayerTimes::new(Utc.ymd(2022, 8, 2), istanbul, params);
+        let date = Utc.ymd(2022, 8, 2);
+
+        assert_eq!(prayers.fajr.datetime.unwrap(), date.and_hms(1, 8, 0));
+        assert_eq!(prayers.sunrise.datetime.unwrap(), date.and_hms(2, 54, 0));
+        assert_eq!(prayers.dhuhr.datetime.unwrap(), date.and_hms(10, 15, 0));
+        assert_eq!(prayers.asr.datetime.unwrap(), date.and_hms(14, 9, 0));
+        assert_eq!(prayers.maghrib.datetime.unwrap(), date.and_hms(17, 26, 0));
+        assert_eq!(prayers.isha.datetime.unwrap(), date.and_hms(19, 3, 0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn prayer_times_json_round_trip() {
+        let prayers = tunis_prayer_times();
+
+        let json = serde_json::to_string(&prayers).unwrap();
+        let decoded: PrayerTimes = serde_json::from_str(&json).unwrap();
+
+        assert!(json.contains("\"fajr\":{\"datetime\":\"2022-08-01T"));
+        assert_eq!(decoded, prayers);
+    }
+}
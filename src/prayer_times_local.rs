@@ -0,0 +1,96 @@
+use chrono::{DateTime, TimeZone};
+
+use crate::models::prayer::Prayer;
+use crate::prayer_times::PrayerTimes;
+
+/// Prayer times converted to a local timezone, e.g. for display
+#[derive(PartialEq, Debug, Clone)]
+pub struct PrayerTimesLocal<Tz: TimeZone> {
+    pub imsak: Option<DateTime<Tz>>,
+    pub fajr: Option<DateTime<Tz>>,
+    pub sunrise: Option<DateTime<Tz>>,
+    pub duha: Option<DateTime<Tz>>,
+    pub dhuhr: Option<DateTime<Tz>>,
+    pub asr: Option<DateTime<Tz>>,
+    pub maghrib: Option<DateTime<Tz>>,
+    pub isha: Option<DateTime<Tz>>,
+    pub middle_of_the_night: Option<DateTime<Tz>>,
+    pub qiyam: Option<DateTime<Tz>>,
+    pub fajr_tomorrow: Option<DateTime<Tz>>,
+}
+
+impl<Tz: TimeZone> PrayerTimesLocal<Tz> {
+    pub fn new(prayer_times: &PrayerTimes, timezone: Tz) -> PrayerTimesLocal<Tz> {
+        let local = |prayer: Prayer| {
+            prayer_times
+                .prayer_time(prayer)
+                .datetime
+                .map(|datetime| datetime.with_timezone(&timezone))
+        };
+
+        PrayerTimesLocal {
+            imsak: local(Prayer::Imsak),
+            fajr: local(Prayer::Fajr),
+            sunrise: local(Prayer::Sunrise),
+            duha: local(Prayer::Duha),
+            dhuhr: local(Prayer::Dhuhr),
+            asr: local(Prayer::Asr),
+            maghrib: local(Prayer::Maghrib),
+            isha: local(Prayer::Isha),
+            middle_of_the_night: local(Prayer::MiddleOfTheNight),
+            qiyam: local(Prayer::Qiyam),
+            fajr_tomorrow: local(Prayer::FajrTomorrow),
+        }
+    }
+
+    /// Returns the local time of the given prayer, if any
+    pub fn time(&self, prayer: Prayer) -> Option<DateTime<Tz>> {
+        match prayer {
+            Prayer::Imsak => self.imsak.clone(),
+            Prayer::Fajr => self.fajr.clone(),
+            Prayer::Sunrise => self.sunrise.clone(),
+            Prayer::Duha => self.duha.clone(),
+            Prayer::Dhuhr => self.dhuhr.clone(),
+            Prayer::Asr => self.asr.clone(),
+            Prayer::Maghrib => self.maghrib.clone(),
+            Prayer::Isha => self.isha.clone(),
+            Prayer::MiddleOfTheNight => self.middle_of_the_night.clone(),
+            Prayer::Qiyam => self.qiyam.clone(),
+            Prayer::FajrTomorrow => self.fajr_tomorrow.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "chrono-tz")]
+    use crate::models::prayer::Prayer;
+    use crate::prayer_times::test_support::tunis_prayer_times;
+    use chrono::{FixedOffset, Timelike};
+
+    #[test]
+    fn convert_prayer_times_to_fixed_offset() {
+        let prayers = tunis_prayer_times();
+        let local = prayers.in_timezone(FixedOffset::east(3600));
+
+        for (prayer, _) in prayers.iter() {
+            let time = local.time(prayer).unwrap();
+
+            assert_eq!(time, prayers.time(prayer));
+            assert_eq!(time.hour(), (prayers.time(prayer).hour() + 1) % 24);
+        }
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn convert_prayer_times_to_tunis_timezone() {
+        let prayers = tunis_prayer_times();
+        let local = prayers.in_timezone(chrono_tz::Africa::Tunis);
+
+        assert_eq!(local.maghrib.unwrap(), prayers.time(Prayer::Maghrib));
+        assert_eq!(
+            local.maghrib.unwrap().naive_local(),
+            prayers.time(Prayer::Maghrib).naive_utc() + chrono::Duration::hours(1)
+        );
+    }
+}
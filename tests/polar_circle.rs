@@ -0,0 +1,42 @@
+use salati::prelude::*;
+
+#[test]
+fn umm_al_qura_resolution_during_polar_night() {
+    let coordinates = Coordinates::new(70.0, 23.0);
+    let date = Utc.ymd(2022, 12, 21);
+    let params = ParametersBuilder::with(Method::MuslimWorldLeague, Madhab::Shafi);
+    assert_eq!(
+        params.polar_circle_resolution,
+        PolarCircleResolution::UmmAlQura
+    );
+
+    let prayers = PrayerTimes::new(date, coordinates, params);
+    let sunrise = prayers.solar_sunrise.datetime.unwrap();
+    let sunset = prayers.solar_sunset.datetime.unwrap();
+    let fajr = prayers.time(Prayer::Fajr);
+    let isha = prayers.time(Prayer::Isha);
+
+    for prayer in [
+        Prayer::Fajr,
+        Prayer::Sunrise,
+        Prayer::Dhuhr,
+        Prayer::Asr,
+        Prayer::Maghrib,
+        Prayer::Isha,
+    ] {
+        assert_eq!(
+            prayers.prayer_time(prayer).code,
+            PrayerTimeResolution::PolarCircle
+        );
+    }
+
+    assert!(fajr < sunrise);
+    assert!(sunrise < prayers.time(Prayer::Dhuhr));
+    assert!(prayers.time(Prayer::Dhuhr) < prayers.time(Prayer::Asr));
+    assert!(prayers.time(Prayer::Asr) < prayers.time(Prayer::Maghrib));
+    assert!((isha - sunset - Duration::minutes(90)).num_minutes().abs() <= 1);
+
+    // Fajr starts at the last seventh of the night
+    let night = sunrise - (sunset - Duration::days(1));
+    assert!((sunrise - fajr - night / 7).num_minutes().abs() <= 1);
+}